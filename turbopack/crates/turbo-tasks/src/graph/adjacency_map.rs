@@ -7,21 +7,57 @@ use super::graph_store::{GraphNode, GraphStore};
 use crate as turbo_tasks;
 
 /// A graph traversal that builds an adjacency map
+///
+/// Edges can carry a label `E`, e.g. whether a module is imported statically or
+/// dynamically, see [`EdgeLabel`]. Unlabeled maps use `()` as the label.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, ValueDebugFormat)]
-pub struct AdjacencyMap<T>
+pub struct AdjacencyMap<T, E = ()>
 where
     T: Eq + std::hash::Hash + Clone,
 {
-    adjacency_map: HashMap<T, Vec<T>>,
-    roots: Vec<T>,
+    adjacency_map: HashMap<T, Vec<(E, T)>>,
+    roots: Vec<(E, T)>,
 }
 
-impl<T> Default for AdjacencyMap<T>
+/// Marks a type as the edge label of an [`AdjacencyMap`].
+///
+/// A graph traversal into an `AdjacencyMap<T, E>` visits `(label, node)`
+/// pairs, where the label describes the edge from the parent to `node`. Roots
+/// have a label too, which describes how they were reached. Nodes are
+/// identified without their label, so a node reached through differently
+/// labeled edges is a single node with multiple incoming edges. Every edge is
+/// recorded, but a node that was already visited through any label is not
+/// visited again, so the map doesn't need to be wrapped in
+/// [`SkipDuplicates`](super::SkipDuplicates). Every visited node has an entry,
+/// so [`AdjacencyMap::get`] returns an empty iterator for leaves.
+pub trait EdgeLabel {}
+
+/// A summary of an [`AdjacencyMap`], as returned by [`AdjacencyMap::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphStats {
+    /// The number of distinct nodes, including roots and leaves.
+    pub node_count: usize,
+    /// The number of edges, excluding the implicit edges to the roots.
+    pub edge_count: usize,
+    /// The number of distinct roots.
+    pub root_count: usize,
+    /// The number of nodes without children.
+    pub leaf_count: usize,
+    /// The largest number of children of a single node.
+    pub max_out_degree: usize,
+    /// Whether any node is part of a cycle.
+    pub has_cycle: bool,
+}
+
+impl<T, E> Default for AdjacencyMap<T, E>
 where
     T: Eq + std::hash::Hash + Clone,
 {
     fn default() -> Self {
-        Self::new()
+        Self {
+            adjacency_map: HashMap::new(),
+            roots: Vec::new(),
+        }
     }
 }
 
@@ -30,22 +66,1087 @@ where
     T: Eq + std::hash::Hash + Clone,
 {
     /// Creates a new adjacency map
+    ///
+    /// Use [`Default`] to create a map with labeled edges.
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an adjacency map from roots and a list of `(from, to)` edges, as
+    /// returned by [`AdjacencyMap::to_edge_list`].
+    ///
+    /// The children of each node keep the order of the edge list.
+    pub fn from_edge_list(roots: Vec<T>, edges: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut adjacency_map: HashMap<T, Vec<((), T)>> = HashMap::new();
+        for (from, to) in edges {
+            adjacency_map.entry(from).or_default().push(((), to));
+        }
         Self {
-            adjacency_map: HashMap::new(),
-            roots: Vec::new(),
+            adjacency_map,
+            roots: roots.into_iter().map(|root| ((), root)).collect(),
+        }
+    }
+
+    /// Returns the roots and all `(from, to)` edges of the graph, with the
+    /// edges sorted so the result is stable across runs.
+    ///
+    /// Nodes that are neither a root nor part of an edge are not represented.
+    pub fn to_edge_list(&self) -> (Vec<T>, Vec<(T, T)>)
+    where
+        T: Ord,
+    {
+        let mut edges: Vec<(T, T)> = self
+            .adjacency_map
+            .iter()
+            .flat_map(|(from, children)| {
+                children
+                    .iter()
+                    .map(move |(_, to)| (from.clone(), to.clone()))
+            })
+            .collect();
+        edges.sort();
+        (self.roots().cloned().collect(), edges)
+    }
+
+    /// Returns the subgraph of all nodes that lie on some path from `from` to
+    /// `to`, keeping only the edges between those nodes. `from` is the root of
+    /// the subgraph.
+    ///
+    /// The subgraph is empty if `to` can't be reached from `from`.
+    pub fn subgraph_between(&self, from: &T, to: &T) -> AdjacencyMap<T> {
+        let mut descendants = self.descendants(from);
+        descendants.insert(from);
+        let mut ancestors = reachable_through(&self.parents_map(), to);
+        ancestors.insert(to);
+        let between: HashSet<&T> = descendants.intersection(&ancestors).copied().collect();
+        if !between.contains(from) || !between.contains(to) {
+            return Self::new();
+        }
+
+        let adjacency_map = between
+            .iter()
+            .filter_map(|node| {
+                let children: Vec<((), T)> = self
+                    .children_of(node)
+                    .filter(|child| between.contains(child))
+                    .map(|child| ((), child.clone()))
+                    .collect();
+                (!children.is_empty()).then(|| ((*node).clone(), children))
+            })
+            .collect();
+        Self {
+            adjacency_map,
+            roots: vec![((), from.clone())],
+        }
+    }
+
+    /// Renames `old` to `new`, keeping all of its incoming and outgoing edges.
+    ///
+    /// If `new` is already part of the graph, the edges of both nodes are
+    /// merged without duplicates.
+    pub fn replace_node(&mut self, old: &T, new: T) {
+        if *old == new {
+            return;
+        }
+
+        fn replace_in<T: Eq + Clone>(nodes: &mut Vec<((), T)>, old: &T, new: &T) {
+            if let Some(index) = nodes.iter().position(|(_, node)| node == old) {
+                nodes.retain(|(_, node)| node != old);
+                if !nodes.iter().any(|(_, node)| node == new) {
+                    nodes.insert(index, ((), new.clone()));
+                }
+            }
+        }
+
+        replace_in(&mut self.roots, old, &new);
+        for children in self.adjacency_map.values_mut() {
+            replace_in(children, old, &new);
+        }
+        if let Some(children) = self.adjacency_map.remove(old) {
+            let new_children = self.adjacency_map.entry(new).or_default();
+            for child in children {
+                if !new_children.contains(&child) {
+                    new_children.push(child);
+                }
+            }
+        }
+    }
+
+    /// Merges all nodes with the same key returned from `key_fn` into a single
+    /// canonical node, e.g. to collapse different identities of the same
+    /// module.
+    ///
+    /// The canonical node of a key is the first one in the order described in
+    /// [`AdjacencyMap::find_node`]. Edges and roots are rewritten to the
+    /// canonical nodes without duplicates. Edges between two merged nodes are
+    /// dropped instead of turning into self-loops.
+    pub fn merge_nodes_by<K, F>(&mut self, key_fn: F)
+    where
+        T: Ord,
+        K: Eq + std::hash::Hash,
+        F: Fn(&T) -> K,
+    {
+        let nodes = self.ordered_nodes();
+        let mut canonical_by_key: HashMap<K, &T> = HashMap::new();
+        let canonical: HashMap<&T, &T> = nodes
+            .iter()
+            .map(|node| (*node, *canonical_by_key.entry(key_fn(node)).or_insert(node)))
+            .collect();
+
+        let mut roots: Vec<((), T)> = Vec::new();
+        for root in self.roots() {
+            let root = canonical[root];
+            if !roots.iter().any(|(_, merged)| merged == root) {
+                roots.push(((), root.clone()));
+            }
+        }
+        let mut adjacency_map: HashMap<T, Vec<((), T)>> = HashMap::new();
+        for node in &nodes {
+            let Some(children) = self.adjacency_map.get(*node) else {
+                continue;
+            };
+            let from = canonical[node];
+            let merged = adjacency_map.entry(from.clone()).or_default();
+            for (_, child) in children {
+                let to = canonical[child];
+                if (to != from || child == *node) && !merged.iter().any(|(_, merged)| merged == to)
+                {
+                    merged.push(((), to.clone()));
+                }
+            }
+        }
+
+        self.roots = roots;
+        self.adjacency_map = adjacency_map;
+    }
+
+    /// Removes the given node by merging it into each of its parents.
+    ///
+    /// Every parent inherits the node's children in place of the edge to the
+    /// node. Edges that would turn into self-loops through the contraction
+    /// are dropped. If the node is a root, its children become roots instead,
+    /// so they stay reachable.
+    pub fn contract_node(&mut self, node: &T) {
+        let children = self.adjacency_map.remove(node).unwrap_or_default();
+        let children = children
+            .iter()
+            .map(|(_, child)| child)
+            .filter(|child| *child != node);
+
+        // Parents that lose their only child are dropped, while other nodes
+        // without children (e.g. from `retain_edges`) are left untouched.
+        let mut emptied_parents = Vec::new();
+        for (parent, parent_children) in self.adjacency_map.iter_mut() {
+            if !parent_children.iter().any(|(_, child)| child == node) {
+                continue;
+            }
+            parent_children.retain(|(_, child)| child != node);
+            for child in children.clone() {
+                if child != parent
+                    && !parent_children
+                        .iter()
+                        .any(|(_, existing)| existing == child)
+                {
+                    parent_children.push(((), child.clone()));
+                }
+            }
+            if parent_children.is_empty() {
+                emptied_parents.push(parent.clone());
+            }
+        }
+        for parent in emptied_parents {
+            self.adjacency_map.remove(&parent);
+        }
+
+        if self.roots().any(|root| root == node) {
+            self.roots.retain(|(_, root)| root != node);
+            for child in children {
+                if !self.roots().any(|root| root == child) {
+                    self.roots.push(((), child.clone()));
+                }
+            }
+        }
+    }
+
+    /// Converts every node with `f`, keeping the structure of the graph.
+    ///
+    /// `f` is called once per distinct node. Nodes that are mapped to the same
+    /// value are merged, with their roots and edges deduplicated.
+    pub fn map_nodes<U, F>(self, f: F) -> AdjacencyMap<U>
+    where
+        U: Eq + std::hash::Hash + Clone,
+        F: Fn(T) -> U,
+    {
+        let mut mapped: HashMap<T, U> = HashMap::new();
+        let mut map = |node: T| {
+            mapped
+                .entry(node)
+                .or_insert_with_key(|node| f(node.clone()))
+                .clone()
+        };
+
+        let mut roots: Vec<((), U)> = Vec::new();
+        for ((), root) in self.roots {
+            let root = ((), map(root));
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        let mut adjacency_map: HashMap<U, Vec<((), U)>> = HashMap::new();
+        for (from, children) in self.adjacency_map {
+            let from = map(from);
+            let children: Vec<((), U)> = children
+                .into_iter()
+                .map(|((), child)| ((), map(child)))
+                .collect();
+            let merged = adjacency_map.entry(from).or_default();
+            for to in children {
+                if !merged.contains(&to) {
+                    merged.push(to);
+                }
+            }
+        }
+
+        AdjacencyMap {
+            adjacency_map,
+            roots,
         }
     }
 
+    /// Removes every node for which `keep` returns `false`.
+    ///
+    /// Each removed node is contracted as in [`AdjacencyMap::contract_node`],
+    /// so its parents are connected to its children. Removed nodes are
+    /// bypassed transitively: a parent is connected to the closest kept
+    /// descendants along every path through removed nodes, e.g. removing `2`
+    /// and `3` from `1 -> 2 -> 3 -> 4` leaves `1 -> 4`.
+    pub fn filter_nodes<F: Fn(&T) -> bool>(mut self, keep: F) -> Self
+    where
+        T: Ord,
+    {
+        let removed: Vec<T> = self
+            .ordered_nodes()
+            .into_iter()
+            .filter(|node| !keep(node))
+            .cloned()
+            .collect();
+        for node in &removed {
+            self.contract_node(node);
+        }
+        self
+    }
+}
+
+impl<T, E> AdjacencyMap<T, E>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
     /// Returns an iterator over the root nodes of the graph
     pub fn roots(&self) -> impl Iterator<Item = &T> {
+        self.roots.iter().map(|(_, root)| root)
+    }
+
+    /// Returns an iterator over the root nodes of the graph and their labels
+    pub fn roots_labeled(&self) -> impl Iterator<Item = &(E, T)> {
         self.roots.iter()
     }
 
     /// Returns an iterator over the children of the given node
     pub fn get(&self, node: &T) -> Option<impl Iterator<Item = &T>> {
+        self.adjacency_map
+            .get(node)
+            .map(|vec| vec.iter().map(|(_, child)| child))
+    }
+
+    /// Returns an iterator over the children of the given node, together with
+    /// the label of the edge to each child
+    pub fn get_labeled(&self, node: &T) -> Option<impl Iterator<Item = &(E, T)>> {
         self.adjacency_map.get(node).map(|vec| vec.iter())
     }
+
+    /// Returns a copy of the map that only contains the edges whose label
+    /// matches `keep`.
+    ///
+    /// Roots are kept regardless of their label. Nodes that are only reachable
+    /// through removed edges are not visited by the traversals of the result.
+    pub fn filter_edges(&self, keep: impl Fn(&E) -> bool) -> Self
+    where
+        E: Clone,
+    {
+        let mut map = self.clone();
+        for children in map.adjacency_map.values_mut() {
+            children.retain(|(label, _)| keep(label));
+        }
+        map
+    }
+
+    /// Consumes the map and returns an adjacency map with the same edges,
+    /// without their labels.
+    pub fn into_unlabeled(self) -> AdjacencyMap<T> {
+        AdjacencyMap {
+            adjacency_map: self
+                .adjacency_map
+                .into_iter()
+                .map(|(node, children)| {
+                    let children = children.into_iter().map(|(_, child)| ((), child));
+                    (node, children.collect())
+                })
+                .collect(),
+            roots: self.roots.into_iter().map(|(_, root)| ((), root)).collect(),
+        }
+    }
+
+    /// Returns the number of distinct nodes reachable from the given node,
+    /// including the node itself.
+    ///
+    /// Unlike collecting a traversal into a `Vec`, this only keeps track of
+    /// the visited set. Cycles are handled and each node is counted once.
+    ///
+    /// The count always matches
+    /// `reverse_topological_from_node(node).count()`, which also includes the
+    /// start node.
+    pub fn reachable_count_from(&self, node: &T) -> usize {
+        let mut visited = HashSet::new();
+        let mut stack = vec![node];
+
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+
+            stack.extend(
+                self.children_of(current)
+                    .filter(|neighbor| !visited.contains(neighbor)),
+            );
+        }
+
+        visited.len()
+    }
+
+    /// Returns whether there is a direct edge from `from` to `to`.
+    ///
+    /// Use [`AdjacencyMap::has_path`] to also consider indirect paths.
+    pub fn edge_exists(&self, from: &T, to: &T) -> bool {
+        self.children_of(from).any(|child| child == to)
+    }
+
+    /// Returns whether `to` is reachable from `from` by following any number
+    /// of edges.
+    ///
+    /// Every node is reachable from itself.
+    pub fn has_path(&self, from: &T, to: &T) -> bool {
+        self.path_exists_avoiding(from, to, &HashSet::new())
+    }
+
+    /// Returns whether `to` is reachable from `from` without passing through
+    /// any of the nodes in `avoid`.
+    ///
+    /// Returns `false` if `from` or `to` is itself in `avoid`.
+    pub fn path_exists_avoiding(&self, from: &T, to: &T, avoid: &HashSet<T>) -> bool {
+        if avoid.contains(from) || avoid.contains(to) {
+            return false;
+        }
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+
+        while let Some(current) = stack.pop() {
+            if current == to {
+                return true;
+            }
+            if !visited.insert(current) {
+                continue;
+            }
+
+            stack.extend(
+                self.children_of(current)
+                    .filter(|neighbor| !avoid.contains(neighbor) && !visited.contains(neighbor)),
+            );
+        }
+
+        false
+    }
+
+    /// Returns the minimum number of edges on a path from any root to `node`,
+    /// or `None` if `node` is not reachable from the roots.
+    ///
+    /// Roots have a depth of 0.
+    pub fn depth_of(&self, node: &T) -> Option<usize> {
+        let mut visited: HashSet<&T> = HashSet::new();
+        let mut queue: VecDeque<(&T, usize)> = VecDeque::new();
+        for root in self.roots() {
+            if visited.insert(root) {
+                queue.push_back((root, 0));
+            }
+        }
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if current == node {
+                return Some(depth);
+            }
+
+            for neighbor in self.children_of(current) {
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns a summary of the size and shape of the graph.
+    ///
+    /// The counts are computed in a single pass over the adjacency map, the
+    /// cycle check is the same as [`AdjacencyMap::find_all_cycles`].
+    pub fn stats(&self) -> GraphStats {
+        let mut nodes: HashSet<&T> = self.roots().collect();
+        let root_count = nodes.len();
+        let mut edge_count = 0;
+        let mut max_out_degree = 0;
+        let mut non_leaf_count = 0;
+        for (node, children) in &self.adjacency_map {
+            nodes.insert(node);
+            nodes.extend(children.iter().map(|(_, child)| child));
+            edge_count += children.len();
+            max_out_degree = max_out_degree.max(children.len());
+            if !children.is_empty() {
+                non_leaf_count += 1;
+            }
+        }
+
+        GraphStats {
+            node_count: nodes.len(),
+            edge_count,
+            root_count,
+            leaf_count: nodes.len() - non_leaf_count,
+            max_out_degree,
+            has_cycle: !self.find_all_cycles().is_empty(),
+        }
+    }
+
+    /// Groups every node of the graph, including roots and leaves, by the key
+    /// returned from `key_fn`.
+    ///
+    /// Each node is part of exactly one group. The order of the nodes within
+    /// a group is unspecified.
+    pub fn group_by<K: Eq + std::hash::Hash, F: Fn(&T) -> K>(
+        &self,
+        key_fn: F,
+    ) -> HashMap<K, Vec<&T>> {
+        let mut groups: HashMap<K, Vec<&T>> = HashMap::new();
+        for node in self.all_nodes() {
+            groups.entry(key_fn(node)).or_default().push(node);
+        }
+        groups
+    }
+
+    /// Returns the first node matching `predicate`.
+    ///
+    /// The roots are visited first in their original order, followed by all
+    /// remaining nodes (including leaves) in ascending order.
+    pub fn find_node<F: Fn(&T) -> bool>(&self, predicate: F) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.ordered_nodes()
+            .into_iter()
+            .find(|node| predicate(node))
+    }
+
+    /// Returns all nodes matching `predicate`, each at most once, in the order
+    /// described in [`AdjacencyMap::find_node`].
+    pub fn collect_nodes<F: Fn(&T) -> bool>(&self, predicate: F) -> Vec<&T>
+    where
+        T: Ord,
+    {
+        self.ordered_nodes()
+            .into_iter()
+            .filter(|node| predicate(node))
+            .collect()
+    }
+
+    /// Returns the roots in their original order followed by all other nodes in
+    /// ascending order.
+    fn ordered_nodes(&self) -> Vec<&T>
+    where
+        T: Ord,
+    {
+        let roots: HashSet<&T> = self.roots().collect();
+        let mut rest: Vec<&T> = self
+            .all_nodes()
+            .into_iter()
+            .filter(|node| !roots.contains(node))
+            .collect();
+        rest.sort();
+        let mut seen = HashSet::new();
+        self.roots()
+            .filter(|root| seen.insert(*root))
+            .chain(rest)
+            .collect()
+    }
+
+    /// Renders the graph in the Graphviz DOT format, labeling each node with
+    /// `node_label` and drawing the roots as boxes.
+    ///
+    /// See [`AdjacencyMap::to_dot_with`] for custom attributes.
+    pub fn to_dot<L: Fn(&T) -> String>(&self, node_label: L) -> String
+    where
+        T: Ord,
+    {
+        self.to_dot_with(
+            |node| {
+                let mut attributes = vec![("label", node_label(node))];
+                if self.roots().any(|root| root == node) {
+                    attributes.push(("shape", "box".into()));
+                }
+                attributes
+            },
+            |_, _| Vec::new(),
+        )
+    }
+
+    /// Renders the graph in the Graphviz DOT format, with the attributes
+    /// returned by `node_attr` for each node and `edge_attr` for each
+    /// `(from, to)` edge, e.g. `[("label", name), ("color", "red".into())]`.
+    ///
+    /// Attribute values are quoted and escaped. Nodes are numbered in the
+    /// order described in [`AdjacencyMap::find_node`].
+    pub fn to_dot_with<NA, EA>(&self, node_attr: NA, edge_attr: EA) -> String
+    where
+        T: Ord,
+        NA: Fn(&T) -> Vec<(&'static str, String)>,
+        EA: Fn(&T, &T) -> Vec<(&'static str, String)>,
+    {
+        use std::fmt::Write;
+
+        let nodes = self.ordered_nodes();
+        let ids: HashMap<&T, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut dot = String::from("digraph {\n");
+        for (id, node) in nodes.iter().enumerate() {
+            writeln!(dot, "  n{id}{};", dot_attributes(node_attr(node))).unwrap();
+        }
+        for node in &nodes {
+            for child in self.children_of(node) {
+                writeln!(
+                    dot,
+                    "  n{} -> n{}{};",
+                    ids[node],
+                    ids[child],
+                    dot_attributes(edge_attr(node, child))
+                )
+                .unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns all nodes of the graph, which are the roots, the nodes with an
+    /// adjacency entry and their children.
+    fn all_nodes(&self) -> HashSet<&T> {
+        self.roots()
+            .chain(self.adjacency_map.iter().flat_map(|(node, children)| {
+                std::iter::once(node).chain(children.iter().map(|(_, child)| child))
+            }))
+            .collect()
+    }
+
+    /// Returns the nodes reachable from both `a` and `b` through one or more
+    /// edges.
+    pub fn common_descendants(&self, a: &T, b: &T) -> HashSet<T> {
+        let a_descendants = self.descendants(a);
+        self.descendants(b)
+            .into_iter()
+            .filter(|node| a_descendants.contains(node))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the nodes which can reach both `a` and `b` through one or more
+    /// edges.
+    pub fn common_ancestors(&self, a: &T, b: &T) -> HashSet<T> {
+        let parents = self.parents_map();
+        let a_ancestors = reachable_through(&parents, a);
+        reachable_through(&parents, b)
+            .into_iter()
+            .filter(|node| a_ancestors.contains(node))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns an iterator over the nodes with a direct edge to `node`, i.e.
+    /// everything that directly depends on it.
+    ///
+    /// Each parent is yielded once, in no particular order. Only forward edges
+    /// are stored, so every call scans all edges. Use
+    /// [`AdjacencyMap::parents_map`] to look up the parents of many nodes, and
+    /// [`AdjacencyMap::ancestors`] for the transitive dependents.
+    pub fn parents<'a>(&'a self, node: &'a T) -> impl Iterator<Item = &'a T> {
+        self.adjacency_map
+            .iter()
+            .filter(move |(_, children)| children.iter().any(|(_, child)| child == node))
+            .map(|(parent, _)| parent)
+    }
+
+    /// Returns the number of nodes with a direct edge to `node`.
+    ///
+    /// Roots don't count as having an implicit parent. Like
+    /// [`AdjacencyMap::parents`], every call scans all edges.
+    pub fn in_degree(&self, node: &T) -> usize {
+        self.parents(node).count()
+    }
+
+    /// Returns an iterator over every node that can reach `node` through one
+    /// or more edges, i.e. everything that transitively depends on it.
+    ///
+    /// Each node is yielded once, in no particular order. `node` itself is
+    /// only included if it is part of a cycle.
+    pub fn ancestors<'a>(&'a self, node: &'a T) -> impl Iterator<Item = &'a T> {
+        reachable_through(&self.parents_map(), node).into_iter()
+    }
+
+    /// Returns the inverted adjacency map, mapping each node to its parents.
+    ///
+    /// The map is built in a single pass over all edges. Each parent is listed
+    /// once, in no particular order, and nodes without parents are not
+    /// included. The map borrows the graph, so it has to be rebuilt after
+    /// the graph is modified.
+    pub fn parents_map(&self) -> HashMap<&T, Vec<&T>> {
+        let mut parents: HashMap<&T, Vec<&T>> = HashMap::new();
+        for (parent, children) in &self.adjacency_map {
+            for (_, child) in children {
+                let child_parents = parents.entry(child).or_default();
+                // Duplicate edges of a parent are visited in the same iteration.
+                if child_parents.last() != Some(&parent) {
+                    child_parents.push(parent);
+                }
+            }
+        }
+        parents
+    }
+
+    /// Returns the nodes reachable from `node` through one or more edges.
+    ///
+    /// `node` itself is only included if it is part of a cycle.
+    pub fn descendants(&self, node: &T) -> HashSet<&T> {
+        let mut visited = HashSet::new();
+        let mut stack = self.children_of(node).collect::<Vec<_>>();
+        visited.extend(stack.iter().copied());
+        while let Some(current) = stack.pop() {
+            for child in self.children_of(current) {
+                if visited.insert(child) {
+                    stack.push(child);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Returns the nodes reachable from `node` through one or more edges,
+    /// consuming the graph.
+    ///
+    /// `node` itself is only included if it is part of a cycle.
+    pub fn into_descendants(mut self, node: &T) -> HashSet<T> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![node.clone()];
+        while let Some(current) = stack.pop() {
+            for (_, child) in self.adjacency_map.remove(&current).unwrap_or_default() {
+                if visited.insert(child.clone()) {
+                    stack.push(child);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Returns the transitive closure of the graph, mapping every node to the
+    /// set of nodes reachable from it through one or more edges.
+    ///
+    /// A node is only part of its own closure if it is part of a cycle.
+    ///
+    /// The closure is computed once per strongly connected component, in
+    /// reverse topological order, so each component unions the closures of the
+    /// components it points to. Every node still gets its own copy of the set,
+    /// so memory use is quadratic in the number of nodes in the worst case.
+    pub fn transitive_closure(&self) -> HashMap<T, HashSet<T>> {
+        let mut closures: HashMap<T, HashSet<T>> = HashMap::new();
+
+        for component in self.strongly_connected_components() {
+            let mut closure = HashSet::new();
+            for member in &component {
+                for child in self.children_of(member) {
+                    closure.insert(child.clone());
+                    // Children within the same component don't have a closure yet,
+                    // but all of their children are handled by this loop.
+                    if let Some(child_closure) = closures.get(child) {
+                        closure.extend(child_closure.iter().cloned());
+                    }
+                }
+            }
+            for member in component {
+                closures.insert(member.clone(), closure.clone());
+            }
+        }
+
+        closures
+    }
+
+    /// Returns whether the graph has no cycles, including self-loops.
+    ///
+    /// This is a single depth-first search with three-color marking that stops
+    /// at the first back edge, so it is cheaper than
+    /// [`AdjacencyMap::find_all_cycles`] when only acyclicity matters.
+    pub fn is_dag(&self) -> bool {
+        // Nodes on the current path are in `in_progress`, fully explored nodes
+        // in `done`, and everything else is unvisited.
+        let mut in_progress: HashSet<&T> = HashSet::new();
+        let mut done: HashSet<&T> = HashSet::new();
+
+        for start in self.roots().chain(self.adjacency_map.keys()) {
+            if done.contains(start) {
+                continue;
+            }
+            in_progress.insert(start);
+            let mut stack = vec![(start, self.children_of(start))];
+            while let Some((node, children)) = stack.last_mut() {
+                if let Some(child) = children.next() {
+                    if in_progress.contains(child) {
+                        return false;
+                    }
+                    if !done.contains(child) {
+                        in_progress.insert(child);
+                        stack.push((child, self.children_of(child)));
+                    }
+                } else {
+                    in_progress.remove(*node);
+                    done.insert(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns whether the graph is a tree or a forest: it has no cycles, the
+    /// roots have no parents and every other node has at most one parent.
+    pub fn is_tree(&self) -> bool {
+        let parents = self.parents_map();
+        self.roots().all(|root| !parents.contains_key(root))
+            && parents.values().all(|parents| parents.len() <= 1)
+            && self.is_dag()
+    }
+
+    /// Returns a spanning tree of the nodes reachable from the roots, keeping
+    /// only the first edge to each node in breadth first order.
+    pub fn spanning_tree(&self) -> Self
+    where
+        E: Clone,
+    {
+        let mut visited: HashSet<&T> = self.roots().collect();
+        let mut queue: VecDeque<&T> = self.roots().collect();
+        let mut adjacency_map: HashMap<T, Vec<(E, T)>> = HashMap::new();
+        while let Some(node) = queue.pop_front() {
+            for edge @ (_, child) in self.adjacency_map.get(node).into_iter().flatten() {
+                if visited.insert(child) {
+                    adjacency_map
+                        .entry(node.clone())
+                        .or_default()
+                        .push(edge.clone());
+                    queue.push_back(child);
+                }
+            }
+        }
+        Self {
+            adjacency_map,
+            roots: self.roots.clone(),
+        }
+    }
+
+    /// Returns the nodes of a longest path from a root to a leaf, i.e. the
+    /// deepest chain of dependencies.
+    ///
+    /// Ties are broken by the order of the roots and of the children of each
+    /// node. Returns an empty path if the graph has no roots or contains a
+    /// cycle.
+    pub fn critical_path(&self) -> Vec<T> {
+        if !self.is_dag() {
+            return Vec::new();
+        }
+
+        // The number of nodes on the longest path starting at each node, and
+        // the child to continue that path with.
+        let mut longest: HashMap<&T, (usize, Option<&T>)> = HashMap::new();
+        for node in self.reverse_topological() {
+            let mut best = (1, None);
+            for child in self.children_of(node) {
+                let length = longest[child].0 + 1;
+                if length > best.0 {
+                    best = (length, Some(child));
+                }
+            }
+            longest.insert(node, best);
+        }
+
+        let mut path = Vec::new();
+        let mut current = self
+            .roots
+            .iter()
+            .map(|(_, root)| root)
+            .rev()
+            .max_by_key(|root| longest[root].0);
+        while let Some(node) = current {
+            path.push(node.clone());
+            current = longest[node].1;
+        }
+        path
+    }
+
+    /// Partitions the nodes reachable from the roots into layers, so that the
+    /// children of every node are in earlier layers. The nodes of a layer
+    /// don't depend on each other and can be processed concurrently once all
+    /// earlier layers are done.
+    ///
+    /// The layers are the batches of
+    /// [`AdjacencyMap::reverse_topological_parallel_batches`]: leaves are in
+    /// the first layer and every other node is in the layer after its latest
+    /// child. Within a layer, nodes keep their reverse topological order.
+    ///
+    /// Cycles are broken at the same edges as the reverse topological
+    /// iterators. These are the closing edges of the cycles returned by
+    /// [`AdjacencyMap::find_cycles`]. They are ignored when assigning layers
+    /// and returned as `(from, to)` pairs alongside the layers, so callers can
+    /// tell which dependencies are not satisfied by the layer order.
+    pub fn layers(&self) -> (Vec<Vec<T>>, Vec<(T, T)>) {
+        let layers: Vec<Vec<T>> = self.reverse_topological_parallel_batches().collect();
+        let layer_of: HashMap<&T, usize> = layers
+            .iter()
+            .enumerate()
+            .flat_map(|(index, layer)| layer.iter().map(move |node| (node, index)))
+            .collect();
+        // Every other edge points to an earlier layer, because the child was
+        // visited first.
+        let back_edges = layers
+            .iter()
+            .flatten()
+            .flat_map(|node| {
+                self.children_of(node)
+                    .filter(|child| layer_of[child] >= layer_of[node])
+                    .map(move |child| (node.clone(), child.clone()))
+            })
+            .collect();
+        (layers, back_edges)
+    }
+
+    /// Returns the children of the given node, which are empty for leaves.
+    fn children_of(&self, node: &T) -> impl Iterator<Item = &T> + Clone {
+        self.adjacency_map
+            .get(node)
+            .map(|children| children.iter())
+            .unwrap_or_default()
+            .map(|(_, child)| child)
+    }
+
+    /// Returns the groups of nodes that are connected when edges are treated
+    /// as undirected, including nodes that are unreachable from the roots.
+    ///
+    /// Each component is sorted, and the components are ordered by their
+    /// smallest node, so the result doesn't depend on the iteration order of
+    /// the underlying map.
+    pub fn weakly_connected_components(&self) -> Vec<Vec<T>>
+    where
+        T: Ord,
+    {
+        let mut nodes: Vec<&T> = self.all_nodes().into_iter().collect();
+        nodes.sort();
+        let index_of = |node: &T| nodes.binary_search(&node).unwrap();
+
+        // Union-find with path halving, merging into the smaller index.
+        let mut parent: Vec<usize> = (0..nodes.len()).collect();
+        fn find(parent: &mut [usize], mut index: usize) -> usize {
+            while parent[index] != index {
+                parent[index] = parent[parent[index]];
+                index = parent[index];
+            }
+            index
+        }
+        for (node, children) in &self.adjacency_map {
+            let node = index_of(node);
+            for (_, child) in children {
+                let a = find(&mut parent, node);
+                let b = find(&mut parent, index_of(child));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut components: Vec<Vec<T>> = Vec::new();
+        let mut component_of: HashMap<usize, usize> = HashMap::new();
+        for (index, node) in nodes.iter().enumerate() {
+            let component = *component_of
+                .entry(find(&mut parent, index))
+                .or_insert_with(|| {
+                    components.push(Vec::new());
+                    components.len() - 1
+                });
+            components[component].push((*node).clone());
+        }
+        components
+    }
+
+    /// Returns every cycle of the graph as the members of a strongly connected
+    /// component that contains more than one node, or a single node with an
+    /// edge to itself.
+    ///
+    /// Unlike a single example cycle, this covers every node that is part of a
+    /// cycle, but the members of a component are not ordered along the cycle.
+    pub fn find_all_cycles(&self) -> Vec<Vec<T>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter(|component| match component.as_slice() {
+                [node] => self.children_of(node).any(|child| child == *node),
+                _ => true,
+            })
+            .map(|component| component.into_iter().cloned().collect())
+            .collect()
+    }
+
+    /// Returns the cycles which are broken by the reverse topological
+    /// iterators, each as the nodes along the cycle in edge order, starting
+    /// with the node the closing edge points to.
+    ///
+    /// A cycle is reported for every edge that points back to a node on the
+    /// current depth-first path from the roots, so this doesn't enumerate all
+    /// elementary cycles. Use [`AdjacencyMap::find_all_cycles`] to find every
+    /// node which is part of a cycle.
+    pub fn find_cycles(&self) -> Vec<Vec<T>> {
+        let mut cycles = Vec::new();
+        let mut visited = HashSet::new();
+        let mut path: Vec<&T> = Vec::new();
+        let mut path_index: HashMap<&T, usize> = HashMap::new();
+        let mut stack = Vec::new();
+
+        for root in self.roots() {
+            if !visited.insert(root) {
+                continue;
+            }
+            path_index.insert(root, path.len());
+            path.push(root);
+            stack.push(self.children_of(root));
+
+            while let Some(children) = stack.last_mut() {
+                if let Some(child) = children.next() {
+                    if let Some(&index) = path_index.get(child) {
+                        cycles.push(path[index..].iter().map(|&node| node.clone()).collect());
+                    } else if visited.insert(child) {
+                        path_index.insert(child, path.len());
+                        path.push(child);
+                        stack.push(self.children_of(child));
+                    }
+                } else {
+                    stack.pop();
+                    if let Some(node) = path.pop() {
+                        path_index.remove(node);
+                    }
+                }
+            }
+        }
+
+        cycles
+    }
+
+    /// Removes every edge for which `keep(from, to)` returns `false`.
+    ///
+    /// Nodes are never removed, even if they are no longer reachable from the
+    /// roots, so this needs to be followed by a separate prune if unreachable
+    /// nodes should be dropped. The endpoints of a removed edge remain
+    /// visible through [`AdjacencyMap::get`], with an empty list of children
+    /// if they have none left.
+    pub fn retain_edges<F: Fn(&T, &T) -> bool>(&mut self, keep: F) {
+        let mut orphans = Vec::new();
+        for (node, children) in self.adjacency_map.iter_mut() {
+            children.retain(|(_, child)| {
+                let retain = keep(node, child);
+                if !retain {
+                    orphans.push(child.clone());
+                }
+                retain
+            });
+        }
+        for orphan in orphans {
+            self.adjacency_map.entry(orphan).or_default();
+        }
+    }
+
+    /// Returns the strongly connected components of the graph in reverse
+    /// topological order, using Tarjan's algorithm.
+    fn strongly_connected_components(&self) -> Vec<Vec<&T>> {
+        let mut components = Vec::new();
+        let mut index: HashMap<&T, usize> = HashMap::new();
+        let mut lowlink: HashMap<&T, usize> = HashMap::new();
+        let mut component_stack: Vec<&T> = Vec::new();
+        let mut on_stack: HashSet<&T> = HashSet::new();
+
+        for start in self.roots().chain(self.adjacency_map.keys()) {
+            if index.contains_key(start) {
+                continue;
+            }
+
+            index.insert(start, index.len());
+            lowlink.insert(start, index[start]);
+            component_stack.push(start);
+            on_stack.insert(start);
+            let mut call_stack = vec![(start, 0)];
+
+            while let Some((node, next_child)) = call_stack.last_mut() {
+                let node = *node;
+                let children = self
+                    .adjacency_map
+                    .get(node)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                if let Some((_, child)) = children.get(*next_child) {
+                    *next_child += 1;
+                    if let Some(&child_index) = index.get(child) {
+                        if on_stack.contains(child) {
+                            let node_lowlink = lowlink.get_mut(node).unwrap();
+                            *node_lowlink = (*node_lowlink).min(child_index);
+                        }
+                    } else {
+                        index.insert(child, index.len());
+                        lowlink.insert(child, index[child]);
+                        component_stack.push(child);
+                        on_stack.insert(child);
+                        call_stack.push((child, 0));
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                let node_lowlink = lowlink[node];
+                if let Some((parent, _)) = call_stack.last() {
+                    let parent_lowlink = lowlink.get_mut(*parent).unwrap();
+                    *parent_lowlink = (*parent_lowlink).min(node_lowlink);
+                }
+
+                if node_lowlink == index[node] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = component_stack.pop().unwrap();
+                        on_stack.remove(member);
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
 }
 
 impl<T> GraphStore for AdjacencyMap<T>
@@ -64,40 +1165,141 @@ where
             &mut self.roots
         };
 
-        vec.push(node.node().clone());
-        Some((node.into_node(), vec.last().unwrap()))
+        vec.push(((), node.node().clone()));
+        Some((node.into_node(), &vec.last().unwrap().1))
     }
 }
 
-impl<T> AdjacencyMap<T>
+impl<T, E> GraphStore for AdjacencyMap<T, E>
+where
+    T: Eq + std::hash::Hash + Clone,
+    E: EdgeLabel,
+{
+    type Node = (E, T);
+    type Handle = T;
+
+    fn insert(
+        &mut self,
+        from_handle: Option<T>,
+        node: GraphNode<(E, T)>,
+    ) -> Option<(Self::Handle, &(E, T))> {
+        let node = node.into_node();
+        let handle = node.1.clone();
+        // Every visited node has an entry, so a node without one is new.
+        let is_new = !self.adjacency_map.contains_key(&handle);
+        if is_new {
+            self.adjacency_map.insert(handle.clone(), Vec::new());
+        }
+        let edges = if let Some(from_handle) = from_handle {
+            self.adjacency_map.entry(from_handle).or_default()
+        } else {
+            &mut self.roots
+        };
+        edges.push(node);
+        is_new.then(|| (handle, edges.last().unwrap()))
+    }
+}
+
+impl<T, E> AdjacencyMap<T, E>
 where
     T: Eq + std::hash::Hash + Clone,
 {
     /// Returns an owned iterator over the nodes in reverse topological order,
     /// starting from the roots.
-    pub fn into_reverse_topological(self) -> IntoReverseTopologicalIter<T> {
+    pub fn into_reverse_topological(self) -> IntoReverseTopologicalIter<T, E> {
         IntoReverseTopologicalIter {
             adjacency_map: self.adjacency_map,
             stack: self
                 .roots
                 .into_iter()
                 .rev()
-                .map(|root| (ReverseTopologicalPass::Pre, root))
+                .map(|(_, root)| (ReverseTopologicalPass::Pre, root))
                 .collect(),
             visited: HashSet::new(),
         }
     }
 
+    /// Returns an owned iterator over the nodes in reverse topological order,
+    /// starting from the roots, only following the edges for which
+    /// `edge_pred(parent, child)` returns `true`.
+    ///
+    /// Nodes that are only reachable through filtered-out edges are not
+    /// visited.
+    pub fn into_reverse_topological_filtered_edges<F>(
+        mut self,
+        edge_pred: F,
+    ) -> IntoReverseTopologicalIter<T, E>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        for (parent, children) in self.adjacency_map.iter_mut() {
+            children.retain(|(_, child)| edge_pred(parent, child));
+        }
+        self.into_reverse_topological()
+    }
+
+    /// Returns an owned iterator over the nodes in topological order, so every
+    /// node is yielded before its children.
+    ///
+    /// This is exactly the reverse of
+    /// [`AdjacencyMap::into_reverse_topological`], including how cycles are
+    /// broken: an edge that closes a cycle is ignored. The whole graph is
+    /// traversed before the first node is yielded.
+    pub fn into_topological(self) -> impl Iterator<Item = T> {
+        self.into_reverse_topological()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+    }
+
+    /// Returns an owned iterator over the nodes in reverse topological order,
+    /// starting from the roots, yielding them in batches of `batch_size`.
+    ///
+    /// The last batch may contain fewer than `batch_size` nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    pub fn into_reverse_topological_chunked(
+        self,
+        batch_size: usize,
+    ) -> impl Iterator<Item = Vec<T>> {
+        assert!(batch_size > 0, "batch_size must be greater than zero");
+        let mut iter = self.into_reverse_topological();
+        std::iter::from_fn(move || {
+            let batch = iter.by_ref().take(batch_size).collect::<Vec<_>>();
+            (!batch.is_empty()).then_some(batch)
+        })
+    }
+
     /// Returns an owned iterator over all edges (node pairs) in breadth first order,
     /// starting from the roots.
-    pub fn into_breadth_first_edges(self) -> IntoBreadthFirstEdges<T> {
+    pub fn into_breadth_first_edges(self) -> IntoBreadthFirstEdges<T, E> {
         IntoBreadthFirstEdges {
             adjacency_map: self.adjacency_map,
             stack: self
                 .roots
                 .into_iter()
                 .rev()
-                .map(|root| (None, root))
+                .map(|(_, root)| (None, root))
+                .collect(),
+            visited: HashSet::new(),
+        }
+    }
+
+    /// Returns an owned iterator over all edges (node pairs) in depth first
+    /// pre-order, starting from the roots.
+    ///
+    /// Like [`AdjacencyMap::into_breadth_first_edges`], every edge is yielded,
+    /// but the children of a node are only visited once.
+    pub fn into_depth_first_edges(self) -> IntoDepthFirstEdges<T, E> {
+        IntoDepthFirstEdges {
+            adjacency_map: self.adjacency_map,
+            stack: self
+                .roots
+                .into_iter()
+                .rev()
+                .map(|(_, root)| (None, root))
                 .collect(),
             visited: HashSet::new(),
         }
@@ -105,25 +1307,64 @@ where
 
     /// Returns an iterator over the nodes in reverse topological order,
     /// starting from the roots.
-    pub fn reverse_topological(&self) -> ReverseTopologicalIter<T> {
+    pub fn reverse_topological(&self) -> ReverseTopologicalIter<T, E> {
         ReverseTopologicalIter {
             adjacency_map: &self.adjacency_map,
             stack: self
                 .roots
                 .iter()
                 .rev()
-                .map(|root| (ReverseTopologicalPass::Pre, root))
+                .map(|(_, root)| (ReverseTopologicalPass::Pre, root))
                 .collect(),
             visited: HashSet::new(),
         }
     }
 
+    /// Returns an iterator over the nodes in topological order, so every node
+    /// is yielded before its children.
+    ///
+    /// This is exactly the reverse of [`AdjacencyMap::reverse_topological`],
+    /// including how cycles are broken: an edge that closes a cycle is
+    /// ignored. The whole graph is traversed before the first node is yielded.
+    pub fn topological(&self) -> impl Iterator<Item = &T> {
+        self.reverse_topological()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+    }
+
+    /// Returns the nodes reachable from the roots in batches, where every
+    /// child of a node is part of an earlier batch than the node itself.
+    ///
+    /// The nodes of a batch don't depend on each other, so each batch can be
+    /// processed concurrently (e.g. with `try_join_all`) once the previous
+    /// batches are done. Edges that close a cycle are ignored, like in
+    /// [`AdjacencyMap::reverse_topological`].
+    pub fn reverse_topological_parallel_batches(&self) -> impl Iterator<Item = Vec<T>> {
+        let mut layers: HashMap<&T, usize> = HashMap::new();
+        let mut batches: Vec<Vec<T>> = Vec::new();
+        for node in self.reverse_topological() {
+            let layer = self
+                .children_of(node)
+                .filter_map(|child| layers.get(child))
+                .map(|layer| layer + 1)
+                .max()
+                .unwrap_or(0);
+            layers.insert(node, layer);
+            if batches.len() <= layer {
+                batches.resize_with(layer + 1, Vec::new);
+            }
+            batches[layer].push(node.clone());
+        }
+        batches.into_iter()
+    }
+
     /// Returns an iterator over the nodes in reverse topological order,
     /// starting from the given node.
     pub fn reverse_topological_from_node<'graph>(
         &'graph self,
         node: &'graph T,
-    ) -> ReverseTopologicalIter<'graph, T> {
+    ) -> ReverseTopologicalIter<'graph, T, E> {
         ReverseTopologicalIter {
             adjacency_map: &self.adjacency_map,
             stack: vec![(ReverseTopologicalPass::Pre, node)],
@@ -132,6 +1373,45 @@ where
     }
 }
 
+/// Formats DOT attributes as ` [key="value", ...]`, or an empty string if
+/// there are none.
+fn dot_attributes(attributes: Vec<(&'static str, String)>) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let attributes = attributes
+        .into_iter()
+        .map(|(key, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{key}=\"{value}\"")
+        })
+        .collect::<Vec<_>>();
+    format!(" [{}]", attributes.join(", "))
+}
+
+/// Returns the nodes reachable from `node` through one or more edges of
+/// `edges`.
+fn reachable_through<'a, T>(edges: &HashMap<&'a T, Vec<&'a T>>, node: &'a T) -> HashSet<&'a T>
+where
+    T: Eq + std::hash::Hash,
+{
+    let mut visited = HashSet::new();
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        if let Some(next) = edges.get(current) {
+            for &next in next {
+                if visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+    }
+    visited
+}
+
 #[derive(Debug)]
 enum ReverseTopologicalPass {
     Pre,
@@ -140,16 +1420,16 @@ enum ReverseTopologicalPass {
 
 /// An iterator over the nodes of a graph in reverse topological order, starting
 /// from the roots.
-pub struct IntoReverseTopologicalIter<T>
+pub struct IntoReverseTopologicalIter<T, E = ()>
 where
     T: Eq + std::hash::Hash + Clone,
 {
-    adjacency_map: HashMap<T, Vec<T>>,
+    adjacency_map: HashMap<T, Vec<(E, T)>>,
     stack: Vec<(ReverseTopologicalPass, T)>,
     visited: HashSet<T>,
 }
 
-impl<T> Iterator for IntoReverseTopologicalIter<T>
+impl<T, E> Iterator for IntoReverseTopologicalIter<T, E>
 where
     T: Eq + std::hash::Hash + Clone,
 {
@@ -179,7 +1459,7 @@ where
                         neighbors
                             .iter()
                             .rev()
-                            .map(|neighbor| (ReverseTopologicalPass::Pre, neighbor.clone())),
+                            .map(|(_, neighbor)| (ReverseTopologicalPass::Pre, neighbor.clone())),
                     );
                 }
             }
@@ -189,16 +1469,16 @@ where
     }
 }
 
-pub struct IntoBreadthFirstEdges<T>
+pub struct IntoBreadthFirstEdges<T, E = ()>
 where
     T: Eq + std::hash::Hash + Clone,
 {
-    adjacency_map: HashMap<T, Vec<T>>,
+    adjacency_map: HashMap<T, Vec<(E, T)>>,
     stack: VecDeque<(Option<T>, T)>,
     visited: HashSet<T>,
 }
 
-impl<T> Iterator for IntoBreadthFirstEdges<T>
+impl<T, E> Iterator for IntoBreadthFirstEdges<T, E>
 where
     T: Eq + std::hash::Hash + Clone,
 {
@@ -217,7 +1497,7 @@ where
                 neighbors
                     .iter()
                     .rev()
-                    .map(|neighbor| (Some(current.clone()), neighbor.clone())),
+                    .map(|(_, neighbor)| (Some(current.clone()), neighbor.clone())),
             );
         }
 
@@ -225,18 +1505,51 @@ where
     }
 }
 
+pub struct IntoDepthFirstEdges<T, E = ()>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    adjacency_map: HashMap<T, Vec<(E, T)>>,
+    stack: Vec<(Option<T>, T)>,
+    visited: HashSet<T>,
+}
+
+impl<T, E> Iterator for IntoDepthFirstEdges<T, E>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    type Item = (Option<T>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (parent, current) = self.stack.pop()?;
+
+        if self.visited.insert(current.clone()) {
+            if let Some(neighbors) = self.adjacency_map.get(&current) {
+                self.stack.extend(
+                    neighbors
+                        .iter()
+                        .rev()
+                        .map(|(_, neighbor)| (Some(current.clone()), neighbor.clone())),
+                );
+            }
+        }
+
+        Some((parent, current))
+    }
+}
+
 /// An iterator over the nodes of a graph in reverse topological order, starting
 /// from the roots.
-pub struct ReverseTopologicalIter<'graph, T>
+pub struct ReverseTopologicalIter<'graph, T, E = ()>
 where
     T: Eq + std::hash::Hash + Clone,
 {
-    adjacency_map: &'graph HashMap<T, Vec<T>>,
+    adjacency_map: &'graph HashMap<T, Vec<(E, T)>>,
     stack: Vec<(ReverseTopologicalPass, &'graph T)>,
     visited: HashSet<&'graph T>,
 }
 
-impl<'graph, T> Iterator for ReverseTopologicalIter<'graph, T>
+impl<'graph, T, E> Iterator for ReverseTopologicalIter<'graph, T, E>
 where
     T: Eq + std::hash::Hash + Clone,
 {
@@ -266,7 +1579,7 @@ where
                         neighbors
                             .iter()
                             .rev()
-                            .map(|neighbor| (ReverseTopologicalPass::Pre, neighbor)),
+                            .map(|(_, neighbor)| (ReverseTopologicalPass::Pre, neighbor)),
                     );
                 }
             }
@@ -275,3 +1588,821 @@ where
         Some(current)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(roots: &[u32], edges: &[(u32, u32)]) -> AdjacencyMap<u32> {
+        let mut map = AdjacencyMap::new();
+        for root in roots {
+            map.insert(None, GraphNode(*root));
+        }
+        for (from, to) in edges {
+            map.insert(Some(*from), GraphNode(*to));
+        }
+        map
+    }
+
+    #[test]
+    fn reachable_count_from() {
+        // 1 -> 2 -> 3 -> 1 (cycle), 2 -> 4, 5 is unreachable from 1
+        let map = graph(&[1, 5], &[(1, 2), (2, 3), (3, 1), (2, 4), (5, 4)]);
+
+        for node in [1, 2, 3, 4, 5] {
+            // `descendants` only includes the start node if it's part of a cycle.
+            let descendants = map.descendants(&node);
+            let start = usize::from(!descendants.contains(&node));
+            assert_eq!(map.reachable_count_from(&node), descendants.len() + start);
+            assert_eq!(
+                map.reachable_count_from(&node),
+                map.reverse_topological_from_node(&node).count()
+            );
+        }
+        assert_eq!(map.reachable_count_from(&1), 4);
+        assert_eq!(map.reachable_count_from(&4), 1);
+        assert_eq!(map.reachable_count_from(&5), 2);
+    }
+
+    #[test]
+    fn path_exists_avoiding() {
+        // Diamond: 1 -> 2 -> 4, 1 -> 3 -> 4, plus a shared node 5 that is the
+        // only connection between 6 and 7.
+        let map = graph(&[1, 6], &[(1, 2), (1, 3), (2, 4), (3, 4), (6, 5), (5, 7)]);
+
+        assert!(map.path_exists_avoiding(&1, &4, &HashSet::new()));
+        assert!(map.path_exists_avoiding(&1, &4, &HashSet::from([2])));
+        assert!(!map.path_exists_avoiding(&1, &4, &HashSet::from([2, 3])));
+
+        assert!(map.path_exists_avoiding(&6, &7, &HashSet::new()));
+        assert!(!map.path_exists_avoiding(&6, &7, &HashSet::from([5])));
+
+        assert!(!map.path_exists_avoiding(&1, &4, &HashSet::from([1])));
+        assert!(!map.path_exists_avoiding(&1, &4, &HashSet::from([4])));
+        assert!(!map.path_exists_avoiding(&4, &1, &HashSet::new()));
+    }
+
+    #[test]
+    fn into_reverse_topological_chunked() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+        let flat = map.clone().into_reverse_topological().collect::<Vec<_>>();
+
+        let batches = map
+            .clone()
+            .into_reverse_topological_chunked(2)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(batches.concat(), flat);
+
+        let batches = map.into_reverse_topological_chunked(10).collect::<Vec<_>>();
+        assert_eq!(batches, vec![flat]);
+    }
+
+    #[test]
+    fn transitive_closure() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+        let closure = map.transitive_closure();
+
+        assert_eq!(closure.len(), 5);
+        for (node, reachable) in &closure {
+            let expected = map
+                .reverse_topological_from_node(node)
+                .filter(|reachable| *reachable != node)
+                .copied()
+                .collect::<HashSet<_>>();
+            assert_eq!(reachable, &expected, "closure of {node}");
+        }
+    }
+
+    #[test]
+    fn transitive_closure_cycle() {
+        let map = graph(&[1], &[(1, 2), (2, 3), (3, 2), (3, 4), (5, 5)]);
+        let closure = map.transitive_closure();
+
+        assert_eq!(closure[&1], HashSet::from([2, 3, 4]));
+        assert_eq!(closure[&2], HashSet::from([2, 3, 4]));
+        assert_eq!(closure[&3], HashSet::from([2, 3, 4]));
+        assert_eq!(closure[&4], HashSet::new());
+        assert_eq!(closure[&5], HashSet::from([5]));
+    }
+
+    #[test]
+    fn find_all_cycles() {
+        let map = graph(
+            &[1],
+            &[
+                (1, 2),
+                (2, 3),
+                (3, 2),
+                (1, 4),
+                (4, 5),
+                (5, 6),
+                (6, 4),
+                (6, 7),
+                (7, 7),
+            ],
+        );
+
+        let mut cycles = map
+            .find_all_cycles()
+            .into_iter()
+            .map(|mut cycle| {
+                cycle.sort();
+                cycle
+            })
+            .collect::<Vec<_>>();
+        cycles.sort();
+        assert_eq!(cycles, vec![vec![2, 3], vec![4, 5, 6], vec![7]]);
+
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4)]);
+        assert!(map.find_all_cycles().is_empty());
+    }
+
+    #[test]
+    fn depth_of() {
+        // 4 is at depth 3 from root 1 and at depth 2 from root 5.
+        let map = graph(&[1, 5], &[(1, 2), (2, 3), (3, 4), (5, 6), (6, 4), (7, 4)]);
+
+        assert_eq!(map.depth_of(&1), Some(0));
+        assert_eq!(map.depth_of(&5), Some(0));
+        assert_eq!(map.depth_of(&3), Some(2));
+        assert_eq!(map.depth_of(&4), Some(2));
+        assert_eq!(map.depth_of(&7), None);
+        assert_eq!(map.depth_of(&8), None);
+    }
+
+    #[test]
+    fn common_descendants() {
+        let map = graph(&[1, 2], &[(1, 3), (2, 3), (1, 4), (2, 5), (4, 6)]);
+
+        assert_eq!(map.common_descendants(&1, &2), HashSet::from([3]));
+        assert_eq!(map.common_descendants(&1, &4), HashSet::from([6]));
+        assert_eq!(map.common_descendants(&3, &5), HashSet::new());
+    }
+
+    #[test]
+    fn common_ancestors() {
+        let map = graph(&[0], &[(0, 1), (0, 2), (1, 3), (2, 3), (1, 4), (2, 5)]);
+
+        assert_eq!(map.common_ancestors(&4, &5), HashSet::from([0]));
+        assert_eq!(map.common_ancestors(&3, &4), HashSet::from([0, 1]));
+        assert_eq!(map.common_ancestors(&0, &3), HashSet::new());
+    }
+
+    #[test]
+    fn contract_node() {
+        // 1 -> 2 -> 3 -> 4, 3 -> 2
+        let mut map = graph(&[1], &[(1, 2), (2, 3), (3, 4), (3, 2)]);
+
+        map.contract_node(&3);
+        assert_eq!(map, graph(&[1], &[(1, 2), (2, 4)]));
+
+        map.contract_node(&1);
+        assert_eq!(map, graph(&[2], &[(2, 4)]));
+
+        // A parent losing its only child is dropped.
+        let mut map = graph(&[1], &[(1, 2)]);
+        map.contract_node(&2);
+        assert_eq!(map, graph(&[1], &[]));
+    }
+
+    #[test]
+    fn contract_node_keeps_leaf_entries() {
+        let mut map = graph(&[1], &[(1, 2), (2, 3), (1, 4)]);
+        map.retain_edges(|_, to| *to != 4);
+
+        map.contract_node(&2);
+        assert_eq!(map.get(&1).unwrap().collect::<Vec<_>>(), vec![&3]);
+        assert_eq!(map.get(&4).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn stats() {
+        // 1 -> 2 -> 3, 1 -> 3, 1 -> 4, 5 -> 3
+        let map = graph(&[1, 5], &[(1, 2), (2, 3), (1, 3), (1, 4), (5, 3)]);
+
+        let nodes: HashSet<u32> = map
+            .roots()
+            .flat_map(|root| map.reverse_topological_from_node(root))
+            .copied()
+            .collect();
+        let leaves = nodes
+            .iter()
+            .filter(|node| {
+                map.get(node)
+                    .is_none_or(|mut children| children.next().is_none())
+            })
+            .count();
+
+        let stats = map.stats();
+        assert_eq!(stats.node_count, nodes.len());
+        assert_eq!(stats.leaf_count, leaves);
+        assert_eq!(stats.root_count, map.roots().count());
+        assert_eq!(stats.edge_count, 5);
+        assert_eq!(stats.max_out_degree, map.get(&1).unwrap().count());
+        assert!(!stats.has_cycle);
+
+        let map = graph(&[1], &[(1, 2), (2, 1)]);
+        assert!(map.stats().has_cycle);
+    }
+
+    #[test]
+    fn is_dag() {
+        assert!(graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4)]).is_dag());
+        assert!(!graph(&[1], &[(1, 2), (2, 2)]).is_dag());
+        assert!(!graph(&[1], &[(1, 2), (2, 3), (3, 1)]).is_dag());
+        // The cycle is not reachable from the roots.
+        assert!(!graph(&[1], &[(4, 5), (5, 4)]).is_dag());
+    }
+
+    #[test]
+    fn weakly_connected_components() {
+        // 1 -> 2 <- 3 and 4 -> 5 -> 6, where 3 is unreachable from the roots
+        let map = graph(&[1, 4], &[(1, 2), (3, 2), (4, 5), (5, 6)]);
+
+        assert_eq!(
+            map.weakly_connected_components(),
+            vec![vec![1, 2, 3], vec![4, 5, 6]]
+        );
+    }
+
+    #[test]
+    fn retain_edges() {
+        let mut map = graph(&[1], &[(1, 2), (1, 3), (3, 4)]);
+
+        map.retain_edges(|from, to| !(*from == 1 && *to == 3));
+
+        assert_eq!(map.get(&1).unwrap().collect::<Vec<_>>(), vec![&2]);
+        assert_eq!(map.get(&3).unwrap().collect::<Vec<_>>(), vec![&4]);
+        assert_eq!(map.reachable_count_from(&1), 2);
+
+        // 4 has no children, but still exists after losing its only parent.
+        map.retain_edges(|_, to| *to != 4);
+        assert_eq!(map.get(&4).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn replace_node() {
+        // 1 -> 2 -> 3, 4 -> 3
+        let mut map = graph(&[1], &[(1, 2), (2, 3), (4, 3)]);
+
+        map.replace_node(&2, 5);
+        assert_eq!(map, graph(&[1], &[(1, 5), (5, 3), (4, 3)]));
+
+        // Replacing with an existing node merges the edges.
+        map.replace_node(&5, 4);
+        assert_eq!(map, graph(&[1], &[(1, 4), (4, 3)]));
+    }
+
+    #[test]
+    fn group_by() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+
+        let mut groups = map.group_by(|node| node % 2 == 0);
+        for group in groups.values_mut() {
+            group.sort();
+        }
+
+        assert_eq!(
+            groups,
+            HashMap::from([(false, vec![&1, &3, &5]), (true, vec![&2, &4])])
+        );
+    }
+
+    #[test]
+    fn ancestors() {
+        // 1 -> 3, 2 -> 3, 0 -> 1, 3 -> 4
+        let map = graph(&[0, 2], &[(0, 1), (1, 3), (2, 3), (3, 4)]);
+
+        let ancestors: HashSet<_> = map.ancestors(&3).collect();
+        assert_eq!(ancestors, HashSet::from([&0, &1, &2]));
+
+        let map = graph(&[1], &[(1, 2), (2, 1)]);
+        let ancestors: HashSet<_> = map.ancestors(&1).collect();
+        assert_eq!(ancestors, HashSet::from([&1, &2]));
+    }
+
+    #[test]
+    fn edge_list() {
+        let map = graph(&[1, 5], &[(1, 2), (1, 3), (2, 4), (3, 4), (5, 4)]);
+
+        let (roots, edges) = map.to_edge_list();
+        assert_eq!(roots, vec![1, 5]);
+        assert!(edges.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(AdjacencyMap::from_edge_list(roots, edges), map);
+    }
+
+    #[test]
+    fn reverse_topological_parallel_batches() {
+        let map = graph(
+            &[1, 6],
+            &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (6, 3), (5, 2)],
+        );
+        let batches = map
+            .reverse_topological_parallel_batches()
+            .collect::<Vec<_>>();
+
+        let mut emitted = HashSet::new();
+        for batch in &batches {
+            for node in batch {
+                for child in map.get(node).into_iter().flatten() {
+                    assert!(
+                        emitted.contains(child) || (*node == 5 && *child == 2),
+                        "{node} was emitted before its dependency {child}"
+                    );
+                }
+            }
+            emitted.extend(batch.iter().copied());
+        }
+        assert_eq!(emitted.len(), 6);
+        assert_eq!(batches.iter().map(Vec::len).sum::<usize>(), 6);
+
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4)]);
+        assert_eq!(
+            map.reverse_topological_parallel_batches()
+                .collect::<Vec<_>>(),
+            vec![vec![4], vec![2, 3], vec![1]]
+        );
+    }
+
+    #[test]
+    fn find_node() {
+        let map = graph(&[3, 1, 3], &[(1, 2), (3, 2), (2, 40), (1, 50)]);
+
+        assert_eq!(map.find_node(|node| *node >= 40), Some(&40));
+        assert_eq!(map.find_node(|node| *node % 2 == 1), Some(&3));
+        assert_eq!(map.find_node(|node| *node > 100), None);
+        assert_eq!(map.collect_nodes(|node| *node != 2), vec![&3, &1, &40, &50]);
+    }
+
+    #[test]
+    fn to_dot() {
+        let map = graph(&[2], &[(2, 1), (2, 3), (1, 3)]);
+        let dot = map.to_dot(|node| match node {
+            1 => "a \"quoted\" \\ label".into(),
+            _ => format!("node {node}"),
+        });
+        assert_eq!(
+            dot,
+            concat!(
+                "digraph {\n",
+                "  n0 [label=\"node 2\", shape=\"box\"];\n",
+                "  n1 [label=\"a \\\"quoted\\\" \\\\ label\"];\n",
+                "  n2 [label=\"node 3\"];\n",
+                "  n0 -> n1;\n",
+                "  n0 -> n2;\n",
+                "  n1 -> n2;\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn to_dot_with() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 3)]);
+        let dot = map.to_dot_with(
+            |node| match node {
+                1 => vec![("label", "entry \"a\"".into()), ("color", "red".into())],
+                _ => vec![],
+            },
+            |from, to| match (from, to) {
+                (1, 3) => vec![("label", "import\ndynamic".into())],
+                _ => vec![],
+            },
+        );
+        assert_eq!(
+            dot,
+            concat!(
+                "digraph {\n",
+                "  n0 [label=\"entry \\\"a\\\"\", color=\"red\"];\n",
+                "  n1;\n",
+                "  n2;\n",
+                "  n0 -> n1;\n",
+                "  n0 -> n2 [label=\"import\\ndynamic\"];\n",
+                "  n1 -> n2;\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn subgraph_between() {
+        let map = graph(
+            &[1],
+            &[
+                (1, 2),
+                (1, 3),
+                (2, 4),
+                (3, 4),
+                (4, 5),
+                (1, 6),
+                (6, 7),
+                (2, 7),
+            ],
+        );
+
+        let subgraph = map.subgraph_between(&1, &4);
+        assert_eq!(subgraph.roots().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(
+            subgraph.to_edge_list().1,
+            vec![(1, 2), (1, 3), (2, 4), (3, 4)]
+        );
+
+        assert_eq!(map.subgraph_between(&2, &7).to_edge_list().1, vec![(2, 7)]);
+        assert_eq!(map.subgraph_between(&4, &1), AdjacencyMap::new());
+    }
+
+    #[test]
+    fn merge_nodes_by() {
+        let mut map = graph(
+            &[1, 11],
+            &[(1, 2), (1, 12), (2, 3), (12, 4), (12, 2), (11, 1), (4, 4)],
+        );
+        map.merge_nodes_by(|node| node % 10);
+
+        assert_eq!(map.roots().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(map.get(&2).unwrap().collect::<Vec<_>>(), vec![&3, &4]);
+        assert!(map.get(&12).is_none());
+        assert_eq!(map.to_edge_list().1, vec![(1, 2), (2, 3), (2, 4), (4, 4)]);
+    }
+
+    #[test]
+    fn critical_path() {
+        let edges = [(1, 2), (1, 3), (2, 4), (3, 5), (5, 6), (4, 7), (8, 4)];
+        let map = graph(&[1, 8], &edges);
+
+        let path = map.critical_path();
+        assert_eq!(path, vec![1, 2, 4, 7]);
+        assert!(path
+            .windows(2)
+            .all(|pair| edges.contains(&(pair[0], pair[1]))));
+        assert_eq!(
+            graph(&[3, 2], &[(2, 4), (3, 5)]).critical_path(),
+            vec![3, 5]
+        );
+        assert_eq!(
+            graph(&[2, 3], &[(2, 4), (3, 5)]).critical_path(),
+            vec![2, 4]
+        );
+
+        assert!(AdjacencyMap::<u32>::new().critical_path().is_empty());
+        assert!(graph(&[1], &[(1, 2), (2, 1)]).critical_path().is_empty());
+    }
+
+    #[test]
+    fn is_tree() {
+        assert!(graph(&[1, 5], &[(1, 2), (1, 3), (3, 4)]).is_tree());
+        assert!(!graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4)]).is_tree());
+        assert!(!graph(&[1, 2], &[(1, 2)]).is_tree());
+        assert!(!graph(&[1], &[(1, 2), (3, 4), (4, 3)]).is_tree());
+    }
+
+    #[test]
+    fn spanning_tree() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 1)]);
+        let tree = map.spanning_tree();
+
+        assert!(tree.is_tree());
+        assert_eq!(tree.to_edge_list(), (vec![1], vec![(1, 2), (1, 3), (2, 4)]));
+    }
+
+    #[test]
+    fn into_reverse_topological_filtered_edges() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 5), (5, 4)]);
+
+        assert_eq!(
+            map.clone()
+                .into_reverse_topological_filtered_edges(|_, _| true)
+                .collect::<Vec<_>>(),
+            map.clone().into_reverse_topological().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            map.into_reverse_topological_filtered_edges(|parent, child| (*parent, *child) != (1, 3))
+                .collect::<Vec<_>>(),
+            vec![4, 2, 1]
+        );
+    }
+
+    #[test]
+    fn edge_exists_and_has_path() {
+        let map = graph(&[1], &[(1, 2), (2, 3)]);
+
+        assert!(map.edge_exists(&1, &2));
+        assert!(!map.edge_exists(&1, &3));
+        assert!(!map.edge_exists(&2, &1));
+        assert!(!map.edge_exists(&4, &1));
+
+        assert!(map.has_path(&1, &2));
+        assert!(map.has_path(&1, &3));
+        assert!(map.has_path(&3, &3));
+        assert!(!map.has_path(&3, &1));
+    }
+
+    #[test]
+    fn topological() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+
+        let order = map.topological().copied().collect::<Vec<_>>();
+        assert_eq!(order, vec![1, 3, 2, 4, 5]);
+        assert_eq!(map.clone().into_topological().collect::<Vec<_>>(), order);
+
+        let cyclic = graph(&[1], &[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(
+            cyclic.topological().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn find_cycles() {
+        assert_eq!(graph(&[1], &[(1, 1)]).find_cycles(), vec![vec![1]]);
+        assert_eq!(
+            graph(&[1], &[(1, 2), (2, 3), (3, 2)]).find_cycles(),
+            vec![vec![2, 3]]
+        );
+        assert_eq!(
+            graph(&[1], &[(1, 2), (2, 3), (3, 4), (4, 2), (3, 1)]).find_cycles(),
+            vec![vec![2, 3, 4], vec![1, 2, 3]]
+        );
+        assert!(graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4)])
+            .find_cycles()
+            .is_empty());
+    }
+
+    #[test]
+    fn into_depth_first_edges() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 5)]);
+
+        let edges = map.clone().into_depth_first_edges().collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                (None, 1),
+                (Some(1), 2),
+                (Some(2), 4),
+                (Some(1), 3),
+                (Some(3), 5)
+            ]
+        );
+        assert_ne!(edges, map.into_breadth_first_edges().collect::<Vec<_>>());
+
+        let diamond = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+        assert_eq!(
+            diamond.into_depth_first_edges().collect::<Vec<_>>(),
+            vec![
+                (None, 1),
+                (Some(1), 2),
+                (Some(2), 4),
+                (Some(4), 5),
+                (Some(1), 3),
+                (Some(3), 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn descendants() {
+        let map = graph(&[1, 5], &[(1, 2), (2, 3), (1, 4), (5, 6), (6, 5)]);
+
+        assert_eq!(map.descendants(&1), HashSet::from([&2, &3, &4]));
+        assert_eq!(map.descendants(&3), HashSet::new());
+        assert_eq!(map.descendants(&5), HashSet::from([&5, &6]));
+
+        assert_eq!(map.clone().into_descendants(&1), HashSet::from([2, 3, 4]));
+        assert_eq!(map.into_descendants(&6), HashSet::from([5, 6]));
+    }
+
+    #[test]
+    fn parents() {
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 4)]);
+
+        assert_eq!(
+            map.parents(&4).copied().collect::<HashSet<_>>(),
+            HashSet::from([2, 3, 4])
+        );
+        assert_eq!(map.parents(&2).copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(map.parents(&1).count(), 0);
+
+        assert_eq!(map.in_degree(&4), 3);
+        assert_eq!(map.in_degree(&1), 0);
+        assert_eq!(map.in_degree(&5), 0);
+    }
+
+    #[test]
+    fn parents_map() {
+        let mut map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 4)]);
+        map.insert(Some(1), GraphNode(2));
+
+        let parents = map.parents_map();
+        for node in [1, 2, 3, 4, 5] {
+            let mut expected = map.parents(&node).collect::<Vec<_>>();
+            expected.sort();
+            let mut actual = parents.get(&node).cloned().unwrap_or_default();
+            actual.sort();
+            assert_eq!(actual, expected, "parents of {node}");
+        }
+        assert_eq!(parents[&2], vec![&1]);
+        assert!(!parents.contains_key(&1));
+    }
+
+    #[test]
+    fn map_nodes() {
+        // 1 -> 2 -> 4, 1 -> 3 -> 4
+        let map = graph(&[1], &[(1, 2), (1, 3), (2, 4), (3, 4)]);
+
+        assert_eq!(
+            map.clone().map_nodes(|node| node * 10),
+            graph(&[10], &[(10, 20), (10, 30), (20, 40), (30, 40)])
+        );
+        assert_eq!(
+            map.map_nodes(|node| node / 2),
+            graph(&[0], &[(0, 1), (1, 2)])
+        );
+    }
+
+    #[test]
+    fn filter_nodes() {
+        let chain = graph(&[1], &[(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(
+            chain.clone().filter_nodes(|node| *node != 2),
+            graph(&[1], &[(1, 3), (3, 4)])
+        );
+        assert_eq!(
+            chain.clone().filter_nodes(|node| *node != 2 && *node != 3),
+            graph(&[1], &[(1, 4)])
+        );
+        assert_eq!(
+            chain.filter_nodes(|node| *node != 1),
+            graph(&[2], &[(2, 3), (3, 4)])
+        );
+
+        // 1 -> 2 -> 3, 1 -> 3, 2 -> 1
+        assert_eq!(
+            graph(&[1], &[(1, 2), (2, 3), (1, 3), (2, 1)]).filter_nodes(|node| *node != 2),
+            graph(&[1], &[(1, 3)])
+        );
+    }
+
+    fn assert_children_in_earlier_layers(map: &AdjacencyMap<u32>, layers: &[Vec<u32>]) {
+        let layer_of: HashMap<u32, usize> = layers
+            .iter()
+            .enumerate()
+            .flat_map(|(index, layer)| layer.iter().map(move |node| (*node, index)))
+            .collect();
+        for (node, layer) in &layer_of {
+            for child in map.get(node).into_iter().flatten() {
+                assert!(layer_of[child] < *layer, "{child} is not before {node}");
+            }
+        }
+    }
+
+    #[test]
+    fn layers() {
+        // 1 -> 2 -> 4, 1 -> 3 -> 4, 3 -> 5, 6 -> 5
+        let map = graph(&[1, 6], &[(1, 2), (1, 3), (2, 4), (3, 4), (3, 5), (6, 5)]);
+        let (layers, back_edges) = map.layers();
+        assert_eq!(layers, [vec![4, 5], vec![2, 3, 6], vec![1]]);
+        assert!(back_edges.is_empty());
+        assert_children_in_earlier_layers(&map, &layers);
+        assert_eq!(
+            layers,
+            map.reverse_topological_parallel_batches()
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(graph(&[1], &[]).layers(), (vec![vec![1]], vec![]));
+        assert_eq!(AdjacencyMap::<u32>::new().layers(), (vec![], vec![]));
+    }
+
+    #[test]
+    fn layers_with_cycles() {
+        // 1 -> 2 -> 3 -> 1, 3 -> 4, 4 -> 4
+        let map = graph(&[1], &[(1, 2), (2, 3), (3, 1), (3, 4), (4, 4)]);
+        assert_eq!(map.find_cycles(), [vec![1, 2, 3], vec![4]]);
+
+        let (layers, back_edges) = map.layers();
+        assert_eq!(layers, [vec![4], vec![3], vec![2], vec![1]]);
+        assert_eq!(back_edges, [(4, 4), (3, 1)]);
+
+        // Without the back edges, every child is in an earlier layer.
+        let mut acyclic = map.clone();
+        acyclic.retain_edges(|from, to| !back_edges.contains(&(*from, *to)));
+        assert!(acyclic.is_dag());
+        assert_children_in_earlier_layers(&acyclic, &layers);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Import {
+        Static,
+        Dynamic,
+    }
+
+    impl EdgeLabel for Import {}
+
+    /// Simulates a breadth-first graph traversal over labeled `edges` into
+    /// `store`.
+    fn visit<S: GraphStore<Node = (Import, u32)>>(
+        store: &mut S,
+        roots: &[u32],
+        edges: &[(u32, Import, u32)],
+    ) {
+        let mut queue = VecDeque::new();
+        for &root in roots {
+            if let Some((handle, _)) = store.insert(None, GraphNode((Import::Static, root))) {
+                queue.push_back((handle, root));
+            }
+        }
+        while let Some((handle, node)) = queue.pop_front() {
+            for &(_, label, to) in edges.iter().filter(|(from, ..)| *from == node) {
+                if let Some((child_handle, _)) =
+                    store.insert(Some(handle.clone()), GraphNode((label, to)))
+                {
+                    queue.push_back((child_handle, to));
+                }
+            }
+        }
+    }
+
+    fn labeled_graph(roots: &[u32], edges: &[(u32, Import, u32)]) -> AdjacencyMap<u32, Import> {
+        let mut map = AdjacencyMap::default();
+        visit(&mut map, roots, edges);
+        map
+    }
+
+    #[test]
+    fn get_labeled() {
+        let map = labeled_graph(
+            &[1],
+            &[
+                (1, Import::Static, 2),
+                (1, Import::Dynamic, 3),
+                (2, Import::Dynamic, 3),
+            ],
+        );
+
+        assert_eq!(
+            map.roots_labeled().collect::<Vec<_>>(),
+            [&(Import::Static, 1)]
+        );
+        assert_eq!(
+            map.get_labeled(&1).unwrap().collect::<Vec<_>>(),
+            [&(Import::Static, 2), &(Import::Dynamic, 3)]
+        );
+        assert_eq!(map.get(&2).unwrap().collect::<Vec<_>>(), [&3]);
+        assert_eq!(map.get_labeled(&3).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn filter_edges() {
+        // 1 -static-> 2 -static-> 4
+        // 1 -dynamic-> 3 -static-> 4, 3 -static-> 5
+        let map = labeled_graph(
+            &[1],
+            &[
+                (1, Import::Static, 2),
+                (1, Import::Dynamic, 3),
+                (2, Import::Static, 4),
+                (3, Import::Static, 4),
+                (3, Import::Static, 5),
+            ],
+        );
+
+        let static_only = map.filter_edges(|label| *label == Import::Static);
+        assert_eq!(
+            static_only.into_reverse_topological().collect::<Vec<_>>(),
+            [4, 2, 1]
+        );
+
+        assert_eq!(
+            map.clone().into_reverse_topological().collect::<Vec<_>>(),
+            [4, 2, 5, 3, 1]
+        );
+        let unlabeled = map.into_unlabeled();
+        assert_eq!(
+            unlabeled.to_edge_list(),
+            (vec![1], vec![(1, 2), (1, 3), (2, 4), (3, 4), (3, 5)])
+        );
+    }
+
+    #[test]
+    fn same_node_with_different_labels() {
+        let map = labeled_graph(
+            &[1],
+            &[
+                (1, Import::Static, 2),
+                (1, Import::Dynamic, 2),
+                (2, Import::Static, 3),
+            ],
+        );
+
+        assert_eq!(
+            map.get_labeled(&1).unwrap().collect::<Vec<_>>(),
+            [&(Import::Static, 2), &(Import::Dynamic, 2)]
+        );
+        // Node 2 is only visited once.
+        assert_eq!(map.get(&2).unwrap().collect::<Vec<_>>(), [&3]);
+    }
+}
@@ -3,12 +3,14 @@ mod control_flow;
 mod graph_store;
 mod graph_traversal;
 mod non_deterministic;
+mod topological_list;
 mod visit;
 mod with_future;
 
-pub use adjacency_map::AdjacencyMap;
+pub use adjacency_map::{AdjacencyMap, EdgeLabel, GraphStats};
 pub use control_flow::VisitControlFlow;
 pub use graph_store::{GraphStore, SkipDuplicates};
 pub use graph_traversal::{GraphTraversal, GraphTraversalResult, VisitedNodes};
 pub use non_deterministic::NonDeterministic;
+pub use topological_list::TopologicalList;
 pub use visit::Visit;
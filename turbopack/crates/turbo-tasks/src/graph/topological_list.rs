@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use super::graph_store::{GraphNode, GraphStore};
+
+/// A graph traversal that only keeps what is needed to list the visited nodes
+/// in reverse topological order.
+///
+/// Unlike [`AdjacencyMap`](super::AdjacencyMap), every node is stored once and
+/// edges are stored as indices, so no node is cloned per edge. Nodes that were
+/// already visited are not visited again, so this store doesn't need to be
+/// wrapped in [`SkipDuplicates`](super::SkipDuplicates).
+#[derive(Debug, Clone)]
+pub struct TopologicalList<T>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    nodes: Vec<T>,
+    indices: HashMap<T, usize>,
+    children: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+}
+
+impl<T> Default for TopologicalList<T>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TopologicalList<T>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    /// Creates a new topological list
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            children: Vec::new(),
+            roots: Vec::new(),
+        }
+    }
+
+    /// Returns the number of distinct nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns whether no node was visited.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns an owned iterator over the nodes in reverse topological order,
+    /// starting from the roots.
+    ///
+    /// This yields the same order as
+    /// [`AdjacencyMap::into_reverse_topological`](super::AdjacencyMap::into_reverse_topological)
+    /// for the same traversal.
+    pub fn into_reverse_topological(self) -> impl Iterator<Item = T> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = self
+            .roots
+            .iter()
+            .rev()
+            .map(|&root| (false, root))
+            .collect::<Vec<_>>();
+
+        while let Some((post, index)) = stack.pop() {
+            if post {
+                order.push(index);
+                continue;
+            }
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            stack.push((true, index));
+            stack.extend(
+                self.children[index]
+                    .iter()
+                    .rev()
+                    .map(|&child| (false, child)),
+            );
+        }
+
+        let mut nodes = self.nodes.into_iter().map(Some).collect::<Vec<_>>();
+        order
+            .into_iter()
+            .map(move |index| nodes[index].take().expect("each node is only yielded once"))
+    }
+}
+
+impl<T> GraphStore for TopologicalList<T>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    type Node = T;
+    type Handle = usize;
+
+    fn insert(
+        &mut self,
+        from_handle: Option<usize>,
+        node: GraphNode<T>,
+    ) -> Option<(Self::Handle, &T)> {
+        let (index, is_new) = match self.indices.get(node.node()) {
+            Some(&index) => (index, false),
+            None => {
+                let index = self.nodes.len();
+                self.indices.insert(node.node().clone(), index);
+                self.nodes.push(node.into_node());
+                self.children.push(Vec::new());
+                (index, true)
+            }
+        };
+
+        if let Some(from_handle) = from_handle {
+            self.children[from_handle].push(index);
+        } else {
+            self.roots.push(index);
+        }
+
+        is_new.then(|| (index, &self.nodes[index]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::graph::{AdjacencyMap, SkipDuplicates};
+
+    /// Simulates a breadth-first graph traversal over `edges` into `store`.
+    fn visit<S: GraphStore<Node = u32>>(store: &mut S, roots: &[u32], edges: &[(u32, u32)]) {
+        let mut queue = VecDeque::new();
+        for &root in roots {
+            if let Some((handle, _)) = store.insert(None, GraphNode(root)) {
+                queue.push_back((handle, root));
+            }
+        }
+        while let Some((handle, node)) = queue.pop_front() {
+            for &(_, to) in edges.iter().filter(|(from, _)| *from == node) {
+                if let Some((child_handle, _)) = store.insert(Some(handle.clone()), GraphNode(to)) {
+                    queue.push_back((child_handle, to));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_adjacency_map() {
+        let roots = [1, 6];
+        let edges = [(1, 2), (1, 3), (3, 2), (2, 4), (3, 5), (6, 5), (5, 4)];
+
+        let mut list = TopologicalList::new();
+        visit(&mut list, &roots, &edges);
+        let mut map = SkipDuplicates::new(AdjacencyMap::new());
+        visit(&mut map, &roots, &edges);
+
+        assert_eq!(list.len(), 6);
+        assert_eq!(
+            list.into_reverse_topological().collect::<Vec<_>>(),
+            map.into_inner()
+                .into_reverse_topological()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn cycle() {
+        let mut list = TopologicalList::new();
+        visit(&mut list, &[1], &[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(
+            list.into_reverse_topological().collect::<Vec<_>>(),
+            [3, 2, 1]
+        );
+    }
+}
@@ -137,6 +137,59 @@ impl Issue for ResolvingIssue {
     // TODO add source link
 }
 
+/// A version range in [ResolveOptions::package_overrides] which isn't a valid
+/// semver range, so the override is ignored.
+#[turbo_tasks::value(shared)]
+pub struct InvalidPackageOverrideIssue {
+    pub file_path: ResolvedVc<FileSystemPath>,
+    pub package_name: RcStr,
+    pub range: RcStr,
+    pub error_message: RcStr,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for InvalidPackageOverrideIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Strong("Invalid package override".into()),
+            StyledString::Text(" for ".into()),
+            StyledString::Code(self.package_name.clone()),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        *self.file_path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
+            StyledString::Text(
+                format!(
+                    "\"{}\" is not a valid version range ({}). The override is ignored and the \
+                     nearest copy of the package is used.",
+                    self.range, self.error_message
+                )
+                .into(),
+            )
+            .resolved_cell(),
+        ))
+    }
+}
+
 async fn lookup_import_map(
     import_map: Vc<ImportMap>,
     file_path: Vc<FileSystemPath>,
@@ -7,7 +7,7 @@ use turbo_tasks::{debug::ValueDebugFormat, trace::TraceRawVcs, ReadRef, Resolved
 use turbo_tasks_fs::{FileContent, FileJsonContent, FileSystemPath};
 
 use super::issue::Issue;
-use crate::issue::{IssueExt, IssueStage, OptionStyledString, StyledString};
+use crate::issue::{IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString};
 
 /// PackageJson wraps the parsed JSON content of a `package.json` file. The
 /// wrapper is necessary so that we can reference the [FileJsonContent]'s inner
@@ -86,3 +86,51 @@ impl Issue for PackageJsonIssue {
         ))
     }
 }
+
+/// A main field (e.g. `main`) of a `package.json` which points to a file that
+/// can't be resolved, so resolving falls back to the package's index file.
+#[turbo_tasks::value(shared)]
+pub struct PackageJsonMainFieldIssue {
+    pub path: ResolvedVc<FileSystemPath>,
+    pub field: RcStr,
+    pub value: RcStr,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for PackageJsonMainFieldIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(format!("Invalid \"{}\" field in package.json", self.field).into())
+            .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        *self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
+            StyledString::Text(
+                format!(
+                    "\"{}\" points to \"{}\", which can't be resolved. Falling back to the \
+                     package's index file.",
+                    self.field, self.value
+                )
+                .into(),
+            )
+            .resolved_cell(),
+        ))
+    }
+}
@@ -43,8 +43,62 @@ impl ModuleReferences {
     pub fn empty() -> Vc<Self> {
         Vc::cell(Vec::new())
     }
+
+    /// Resolves all [ModuleReference]s concurrently and pairs each of them
+    /// with its [ModuleResolveResult].
+    ///
+    /// With `fail_fast`, the first error aborts the whole operation. Otherwise
+    /// a reference which fails to resolve is paired with an unresolvable
+    /// result and the error is only logged.
+    #[turbo_tasks::function]
+    pub async fn resolve_all(
+        self: Vc<Self>,
+        fail_fast: bool,
+    ) -> Result<Vc<ResolvedModuleReferences>> {
+        let resolved = self
+            .await?
+            .iter()
+            .map(|&reference| async move {
+                let result = async {
+                    let result = reference.resolve_reference().to_resolved().await?;
+                    // Read the result, so that errors are reported here.
+                    result.await?;
+                    anyhow::Ok(result)
+                }
+                .await;
+                let result = match result {
+                    Ok(result) => result,
+                    Err(err) if !fail_fast => {
+                        // Describing the reference may fail as well, which must not fail
+                        // the other references.
+                        match reference.to_string().await {
+                            Ok(description) => {
+                                tracing::warn!("failed to resolve {}: {:?}", description, err)
+                            }
+                            Err(_) => tracing::warn!("failed to resolve a reference: {:?}", err),
+                        }
+                        ModuleResolveResult::unresolvable().resolved_cell()
+                    }
+                    Err(err) => return Err(err),
+                };
+                Ok((reference, result))
+            })
+            .try_join()
+            .await?;
+        Ok(Vc::cell(resolved))
+    }
 }
 
+/// [ModuleReference]s paired with their [ModuleResolveResult]s, see
+/// [ModuleReferences::resolve_all].
+#[turbo_tasks::value(transparent)]
+pub struct ResolvedModuleReferences(
+    Vec<(
+        ResolvedVc<Box<dyn ModuleReference>>,
+        ResolvedVc<ModuleResolveResult>,
+    )>,
+);
+
 /// A reference that always resolves to a single module.
 #[turbo_tasks::value]
 pub struct SingleModuleReference {
@@ -38,6 +38,31 @@ pub enum CommonJsReferenceSubType {
 #[derive(Debug, Clone, Hash)]
 pub enum ImportWithType {
     Json,
+    /// The contents of the file as a string default export.
+    Text,
+    /// The contents of the file as a `Uint8Array` default export.
+    Bytes,
+}
+
+impl ImportWithType {
+    /// Parses the value of the `type` import attribute.
+    pub fn from_attribute(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(ImportWithType::Json),
+            "text" => Some(ImportWithType::Text),
+            "bytes" => Some(ImportWithType::Bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the `type` import attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportWithType::Json => "json",
+            ImportWithType::Text => "text",
+            ImportWithType::Bytes => "bytes",
+        }
+    }
 }
 
 #[turbo_tasks::value(serialization = "auto_for_input")]
@@ -907,6 +907,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_exports_field_identity_and_wildcard() {
+        let mut map = AliasMap::new();
+        map.insert(AliasPattern::parse("./package.json"), "./package.json");
+        map.insert(AliasPattern::parse("./*"), "./dist/*.js");
+
+        assert_alias_matches!(
+            map,
+            "./package.json",
+            // The exact match should come first.
+            exact("./package.json"),
+            replaced_owned("./dist/package.json.js"),
+        );
+        assert_alias_matches!(map, "./foo", replaced_owned("./dist/foo.js"));
+        assert_alias_matches!(map, "./nested/bar", replaced_owned("./dist/nested/bar.js"));
+        assert_alias_matches!(map, "foo");
+    }
+
     #[test]
     fn test_pattern() {
         let mut map = AliasMap::new();
@@ -6,7 +6,8 @@ use std::{
     iter::once,
 };
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use tracing::{Instrument, Level};
 use turbo_rcstr::RcStr;
@@ -15,29 +16,35 @@ use turbo_tasks::{
     Value, ValueToString, Vc,
 };
 use turbo_tasks_fs::{
-    util::normalize_request, FileSystemEntryType, FileSystemPath, RealPathResult,
+    to_sys_path,
+    util::{normalize_request, sys_to_unix},
+    FileSystemEntryType, FileSystemPath, RealPathResult,
 };
 
 use self::{
     options::{
-        resolve_modules_options, ConditionValue, ImportMapResult, ResolveInPackage,
-        ResolveIntoPackage, ResolveModules, ResolveModulesOptions, ResolveOptions,
+        resolve_modules_options, ConditionValue, ImportMapResult, PackageOverride,
+        ResolveInPackage, ResolveIntoPackage, ResolveModules, ResolveModulesOptions,
+        ResolveOptions, ResolveOverlay,
     },
     origin::{ResolveOrigin, ResolveOriginExt},
     parse::Request,
     pattern::Pattern,
     plugin::BeforeResolvePlugin,
+    pnp::{read_pnp_manifest, zip_archive, PnpManifestFile, PnpZipArchiveIssue},
     remap::{ExportsField, ImportsField},
 };
 use crate::{
     context::AssetContext,
     file_source::FileSource,
     issue::{
-        module::emit_unknown_module_type_error, resolve::ResolvingIssue, IssueExt, IssueSource,
+        module::emit_unknown_module_type_error,
+        resolve::{InvalidPackageOverrideIssue, ResolvingIssue},
+        IssueExt, IssueSource,
     },
     module::{Module, Modules, OptionModule},
     output::{OutputAsset, OutputAssets},
-    package_json::{read_package_json, PackageJsonIssue},
+    package_json::{read_package_json, PackageJsonIssue, PackageJsonMainFieldIssue},
     raw_module::RawModule,
     reference_type::ReferenceType,
     resolve::{
@@ -55,6 +62,7 @@ pub mod origin;
 pub mod parse;
 pub mod pattern;
 pub mod plugin;
+pub mod pnp;
 pub(crate) mod remap;
 
 pub use alias_map::{
@@ -1039,26 +1047,40 @@ impl ResolveResultOption {
     }
 }
 
+/// Returns the real path of `fs_path` if it is a file, or `fs_path` itself
+/// when `preserve_symlinks` is set.
 async fn exists(
     fs_path: Vc<FileSystemPath>,
+    preserve_symlinks: bool,
     refs: &mut Vec<ResolvedVc<Box<dyn Source>>>,
 ) -> Result<Option<ResolvedVc<FileSystemPath>>> {
-    type_exists(fs_path, FileSystemEntryType::File, refs).await
+    type_exists(fs_path, FileSystemEntryType::File, preserve_symlinks, refs).await
 }
 
+/// Returns the real path of `fs_path` if it is a directory, or `fs_path`
+/// itself when `preserve_symlinks` is set.
 async fn dir_exists(
     fs_path: Vc<FileSystemPath>,
+    preserve_symlinks: bool,
     refs: &mut Vec<ResolvedVc<Box<dyn Source>>>,
 ) -> Result<Option<ResolvedVc<FileSystemPath>>> {
-    type_exists(fs_path, FileSystemEntryType::Directory, refs).await
+    type_exists(
+        fs_path,
+        FileSystemEntryType::Directory,
+        preserve_symlinks,
+        refs,
+    )
+    .await
 }
 
 async fn type_exists(
     fs_path: Vc<FileSystemPath>,
     ty: FileSystemEntryType,
+    preserve_symlinks: bool,
     refs: &mut Vec<ResolvedVc<Box<dyn Source>>>,
 ) -> Result<Option<ResolvedVc<FileSystemPath>>> {
-    let result = fs_path.resolve().await?.realpath_with_links().await?;
+    let fs_path = fs_path.resolve().await?;
+    let result = fs_path.realpath_with_links().await?;
     for path in result.symlinks.iter() {
         refs.push(ResolvedVc::upcast(
             FileSource::new(**path).to_resolved().await?,
@@ -1066,7 +1088,11 @@ async fn type_exists(
     }
     let path = result.path;
     Ok(if *path.get_type().await? == ty {
-        Some(path)
+        Some(if preserve_symlinks {
+            fs_path.to_resolved().await?
+        } else {
+            path
+        })
     } else {
         None
     })
@@ -1074,16 +1100,22 @@ async fn type_exists(
 
 async fn any_exists(
     fs_path: Vc<FileSystemPath>,
+    preserve_symlinks: bool,
     refs: &mut Vec<ResolvedVc<Box<dyn Source>>>,
 ) -> Result<Option<(FileSystemEntryType, Vc<FileSystemPath>)>> {
-    let result = fs_path.resolve().await?.realpath_with_links().await?;
+    let fs_path = fs_path.resolve().await?;
+    let result = fs_path.realpath_with_links().await?;
     for path in result.symlinks.iter() {
         refs.push(ResolvedVc::upcast(
             FileSource::new(**path).to_resolved().await?,
         ));
     }
-    let path = result.path;
-    let ty = *path.get_type().await?;
+    let path = if preserve_symlinks {
+        fs_path
+    } else {
+        *result.path
+    };
+    let ty = *result.path.get_type().await?;
     Ok(
         if matches!(
             ty,
@@ -1091,7 +1123,7 @@ async fn any_exists(
         ) {
             None
         } else {
-            Some((ty, *path))
+            Some((ty, path))
         },
     )
 }
@@ -1191,7 +1223,7 @@ pub async fn find_context_file(
     let mut refs = Vec::new();
     for name in &*names.await? {
         let fs_path = lookup_path.join(name.clone());
-        if let Some(fs_path) = exists(fs_path, &mut refs).await? {
+        if let Some(fs_path) = exists(fs_path, false, &mut refs).await? {
             return Ok(FindContextFileResult::Found(fs_path, refs).cell());
         }
     }
@@ -1231,7 +1263,7 @@ pub async fn find_context_file_or_package_key(
 ) -> Result<Vc<FindContextFileResult>> {
     let mut refs = Vec::new();
     let package_json_path = lookup_path.join("package.json".into());
-    if let Some(package_json_path) = exists(package_json_path, &mut refs).await? {
+    if let Some(package_json_path) = exists(package_json_path, false, &mut refs).await? {
         if let Some(json) = &*read_package_json(*package_json_path).await? {
             if json.get(&**package_key).is_some() {
                 return Ok(FindContextFileResult::Found(package_json_path, refs).into());
@@ -1240,7 +1272,7 @@ pub async fn find_context_file_or_package_key(
     }
     for name in &*names.await? {
         let fs_path = lookup_path.join(name.clone());
-        if let Some(fs_path) = exists(fs_path, &mut refs).await? {
+        if let Some(fs_path) = exists(fs_path, false, &mut refs).await? {
             return Ok(FindContextFileResult::Found(fs_path, refs).into());
         }
     }
@@ -1275,6 +1307,26 @@ enum FindPackageItem {
     PackageFile(ResolvedVc<FileSystemPath>),
 }
 
+/// Checks whether the `version` in the package.json of `package_dir`
+/// satisfies `range`.
+async fn package_version_matches(
+    package_dir: Vc<FileSystemPath>,
+    range: &VersionReq,
+    affecting_sources: &mut Vec<ResolvedVc<Box<dyn Source>>>,
+) -> Result<bool> {
+    let package_json_path = package_dir.join("package.json".into());
+    let Some(package_json) = &*read_package_json(package_json_path).await? else {
+        return Ok(false);
+    };
+    affecting_sources.push(ResolvedVc::upcast(
+        FileSource::new(package_json_path).to_resolved().await?,
+    ));
+    Ok(package_json["version"]
+        .as_str()
+        .and_then(|version| Version::parse(version).ok())
+        .is_some_and(|version| range.matches(&version)))
+}
+
 #[turbo_tasks::value]
 struct FindPackageResult {
     packages: Vec<FindPackageItem>,
@@ -1290,6 +1342,21 @@ async fn find_package(
     let mut packages = vec![];
     let mut affecting_sources = vec![];
     let options = options.await?;
+    let package_override = options
+        .package_overrides
+        .iter()
+        .find(|(name, _)| *name == package_name)
+        .map(|(_, package_override)| package_override);
+    if let Some(PackageOverride::Path(dir)) = package_override {
+        if let Some(package_dir) =
+            dir_exists(**dir, options.preserve_symlinks, &mut affecting_sources).await?
+        {
+            return Ok(FindPackageResult::cell(FindPackageResult {
+                packages: vec![FindPackageItem::PackageDirectory(package_dir)],
+                affecting_sources,
+            }));
+        }
+    }
     for resolve_modules in &options.modules {
         match resolve_modules {
             ResolveModules::Nested(root_vc, names) => {
@@ -1300,10 +1367,17 @@ async fn find_package(
                 while lookup_path_value.is_inside_ref(root) {
                     for name in names.iter() {
                         let fs_path = lookup_path.join(name.clone());
-                        if let Some(fs_path) = dir_exists(fs_path, &mut affecting_sources).await? {
+                        if let Some(fs_path) =
+                            dir_exists(fs_path, options.preserve_symlinks, &mut affecting_sources)
+                                .await?
+                        {
                             let fs_path = fs_path.join(package_name.clone());
-                            if let Some(fs_path) =
-                                dir_exists(fs_path, &mut affecting_sources).await?
+                            if let Some(fs_path) = dir_exists(
+                                fs_path,
+                                options.preserve_symlinks,
+                                &mut affecting_sources,
+                            )
+                            .await?
                             {
                                 packages.push(FindPackageItem::PackageDirectory(fs_path));
                             }
@@ -1323,8 +1397,12 @@ async fn find_package(
             } => {
                 let excluded_extensions = excluded_extensions.await?;
                 let package_dir = dir.join(package_name.clone());
-                if let Some((ty, package_dir)) =
-                    any_exists(package_dir, &mut affecting_sources).await?
+                if let Some((ty, package_dir)) = any_exists(
+                    package_dir,
+                    options.preserve_symlinks,
+                    &mut affecting_sources,
+                )
+                .await?
                 {
                     match ty {
                         FileSystemEntryType::Directory => {
@@ -1345,12 +1423,90 @@ async fn find_package(
                         continue;
                     }
                     let package_file = package_dir.append(extension.clone());
-                    if let Some(package_file) = exists(package_file, &mut affecting_sources).await?
+                    if let Some(package_file) = exists(
+                        package_file,
+                        options.preserve_symlinks,
+                        &mut affecting_sources,
+                    )
+                    .await?
                     {
                         packages.push(FindPackageItem::PackageFile(package_file));
                     }
                 }
             }
+            ResolveModules::Pnp(root) => {
+                let Some(PnpManifestFile {
+                    path: manifest_path,
+                    manifest,
+                }) = *read_pnp_manifest(**root).await?
+                else {
+                    continue;
+                };
+                affecting_sources.push(ResolvedVc::upcast(
+                    FileSource::new(*manifest_path).to_resolved().await?,
+                ));
+                let root_value = root.await?;
+                let lookup_path_value = lookup_path.await?;
+                let issuer = if *lookup_path_value == *root_value {
+                    Some("")
+                } else {
+                    root_value.get_path_to(&lookup_path_value)
+                };
+                let manifest = manifest.await?;
+                if let Some(location) =
+                    issuer.and_then(|issuer| manifest.resolve_package(issuer, &package_name))
+                {
+                    if let Some(archive) = zip_archive(location) {
+                        PnpZipArchiveIssue {
+                            manifest_path,
+                            package_name: package_name.clone(),
+                            archive: archive.into(),
+                        }
+                        .resolved_cell()
+                        .emit();
+                        continue;
+                    }
+                    let package_dir = root.join(location.clone());
+                    if let Some(package_dir) = dir_exists(
+                        package_dir,
+                        options.preserve_symlinks,
+                        &mut affecting_sources,
+                    )
+                    .await?
+                    {
+                        packages.push(FindPackageItem::PackageDirectory(package_dir));
+                    }
+                }
+            }
+        }
+    }
+    if let Some(PackageOverride::Version(range)) = package_override {
+        match VersionReq::parse(range) {
+            Ok(range) => {
+                for package in &packages {
+                    let FindPackageItem::PackageDirectory(package_dir) = package else {
+                        continue;
+                    };
+                    if package_version_matches(**package_dir, &range, &mut affecting_sources)
+                        .await?
+                    {
+                        packages = vec![*package];
+                        break;
+                    }
+                }
+            }
+            Err(err) => {
+                // The override is ignored, like an override which no copy of the
+                // package satisfies.
+                InvalidPackageOverrideIssue {
+                    file_path: lookup_path.to_resolved().await?,
+                    package_name: package_name.clone(),
+                    range: range.clone(),
+                    error_message: err.to_string().into(),
+                }
+                .resolved_cell()
+                .emit();
+            }
         }
     }
     Ok(FindPackageResult::cell(FindPackageResult {
@@ -1487,16 +1643,35 @@ pub async fn resolve_inline(
     };
     async {
         let reference_type = Value::new(reference_type);
-        let before_plugins_result =
-            handle_before_resolve_plugins(lookup_path, reference_type.clone(), request, options)
-                .await?;
+        let options_value = options.await?;
+        let before_plugins_result = handle_before_resolve_plugins(
+            &options_value.before_resolve_plugins,
+            lookup_path,
+            reference_type.clone(),
+            request,
+        )
+        .await?;
 
         let raw_result = match before_plugins_result {
             Some(result) => result,
             None => {
-                resolve_internal(lookup_path, request, options)
+                let result = resolve_internal(lookup_path, request, options)
                     .resolve()
+                    .await?;
+                if options_value.fallback_resolve_plugins.is_empty()
+                    || !*result.is_unresolvable().await?
+                {
+                    result
+                } else {
+                    handle_before_resolve_plugins(
+                        &options_value.fallback_resolve_plugins,
+                        lookup_path,
+                        reference_type.clone(),
+                        request,
+                    )
                     .await?
+                    .unwrap_or(result)
+                }
             }
         };
 
@@ -1558,13 +1733,15 @@ pub async fn url_resolve(
     .await
 }
 
+/// Returns the result of the first of `plugins` whose condition matches the
+/// request and which resolves it.
 async fn handle_before_resolve_plugins(
+    plugins: &[ResolvedVc<Box<dyn BeforeResolvePlugin>>],
     lookup_path: Vc<FileSystemPath>,
     reference_type: Value<ReferenceType>,
     request: Vc<Request>,
-    options: Vc<ResolveOptions>,
 ) -> Result<Option<Vc<ResolveResult>>> {
-    for plugin in &options.await?.before_resolve_plugins {
+    for plugin in plugins {
         let condition = plugin.before_resolve_condition().resolve().await?;
         if !condition.await?.matches(request).await? {
             continue;
@@ -1792,7 +1969,7 @@ async fn resolve_internal_inline(
                     }
                 }
                 // Resolve without fragment
-                resolve_relative_request(
+                let result = resolve_relative_request(
                     lookup_path,
                     request,
                     options,
@@ -1802,7 +1979,25 @@ async fn resolve_internal_inline(
                     *force_in_lookup_dir,
                     Vc::cell(RcStr::default()),
                 )
-                .await?
+                .await?;
+                // Relative specifiers are URLs in ESM, so `./a%20b.js` refers to the file
+                // `a b.js`. The literal path is tried first, since CommonJS doesn't decode.
+                match percent_decode_path(path) {
+                    Some(decoded) if *result.is_unresolvable().await? => {
+                        resolve_relative_request(
+                            lookup_path,
+                            request,
+                            options,
+                            options_value,
+                            &decoded,
+                            **query,
+                            *force_in_lookup_dir,
+                            Vc::cell(RcStr::default()),
+                        )
+                        .await?
+                    }
+                    _ => result,
+                }
             }
             Request::Module {
                 module,
@@ -1901,6 +2096,23 @@ async fn resolve_internal_inline(
                 )
                 .await?
             }
+            Request::Uri {
+                protocol,
+                remainder,
+                query,
+                fragment,
+            } if protocol == "file:" => {
+                resolve_file_url(
+                    lookup_path,
+                    request,
+                    options,
+                    has_alias,
+                    remainder,
+                    **query,
+                    **fragment,
+                )
+                .await?
+            }
             Request::Uri {
                 protocol,
                 remainder,
@@ -1970,6 +2182,7 @@ async fn resolve_into_folder(
 ) -> Result<Vc<ResolveResult>> {
     let package_json_path = package_path.join("package.json".into());
     let options_value = options.await?;
+    let mut unresolvable_main_field = None;
 
     for resolve_into_package in options_value.into_package.iter() {
         match resolve_into_package {
@@ -2005,6 +2218,8 @@ async fn resolve_into_folder(
                             ));
                             return Ok(result.cell());
                         }
+                        unresolvable_main_field
+                            .get_or_insert_with(|| (name.clone(), RcStr::from(field_value)));
                     }
                 };
             }
@@ -2030,9 +2245,133 @@ async fn resolve_into_folder(
 
     let request = Request::parse(Value::new(pattern));
 
-    Ok(resolve_internal_inline(*package_path, request, options)
+    let result = resolve_internal_inline(*package_path, request, options)
         .await?
-        .with_request(".".into()))
+        .with_request(".".into());
+
+    // Like Node.js, we fall back to the index file when the main field is broken, but the
+    // package should still be fixed.
+    if let Some((field, value)) = unresolvable_main_field {
+        if !result.await?.is_unresolvable_ref() {
+            PackageJsonMainFieldIssue {
+                path: package_json_path.to_resolved().await?,
+                field,
+                value,
+            }
+            .resolved_cell()
+            .emit();
+        }
+    }
+
+    Ok(result)
+}
+
+/// Resolves a `file:` URL request by converting it to a path inside of the
+/// file system of `lookup_path`.
+async fn resolve_file_url(
+    lookup_path: Vc<FileSystemPath>,
+    request: Vc<Request>,
+    options: Vc<ResolveOptions>,
+    has_alias: bool,
+    remainder: &str,
+    query: Vc<RcStr>,
+    fragment: Vc<RcStr>,
+) -> Result<Vc<ResolveResult>> {
+    let root = lookup_path.root();
+    let path = match file_url_to_path(remainder) {
+        Ok(path) => match to_sys_path(root).await? {
+            Some(root_path) => {
+                let root_path = sys_to_unix(&root_path.to_string_lossy()).into_owned();
+                match path.strip_prefix(root_path.trim_end_matches('/')) {
+                    Some(relative) if relative.is_empty() || relative.starts_with('/') => {
+                        Ok(format!(".{relative}"))
+                    }
+                    _ => Err(anyhow!(
+                        "`{path}` is outside of the project root `{root_path}`"
+                    )),
+                }
+            }
+            None => Err(anyhow!(
+                "file URLs can only be resolved on a disk file system"
+            )),
+        },
+        Err(err) => Err(err),
+    };
+
+    match path {
+        Ok(path) => {
+            let relative = Request::relative(
+                Value::new(Pattern::Constant(path.into())),
+                query,
+                fragment,
+                true,
+            );
+            Box::pin(resolve_internal_inline(root, relative, options)).await
+        }
+        Err(err) => {
+            if !has_alias {
+                ResolvingIssue {
+                    severity: error_severity(options).await?,
+                    request_type: "file URL".to_string(),
+                    request: request.to_resolved().await?,
+                    file_path: lookup_path.to_resolved().await?,
+                    resolve_options: options.to_resolved().await?,
+                    error_message: Some(err.to_string()),
+                    source: None,
+                }
+                .resolved_cell()
+                .emit();
+            }
+            Ok(ResolveResult::unresolvable().cell())
+        }
+    }
+}
+
+/// Converts the part of a `file:` URL after the scheme to an absolute unix
+/// style path. Only an empty or `localhost` host is supported.
+fn file_url_to_path(remainder: &str) -> Result<String> {
+    let remainder = remainder.split(['?', '#']).next().unwrap_or_default();
+    let path = match remainder.strip_prefix("//") {
+        Some(authority_and_path) => {
+            let (authority, path) = authority_and_path
+                .find('/')
+                .map(|index| authority_and_path.split_at(index))
+                .unwrap_or((authority_and_path, ""));
+            if !authority.is_empty() && !authority.eq_ignore_ascii_case("localhost") {
+                bail!(
+                    "file URLs with a host (`{authority}`) are not supported, only an empty host \
+                     or `localhost`"
+                );
+            }
+            path
+        }
+        None => remainder,
+    };
+    if !path.starts_with('/') {
+        bail!("file URLs must contain an absolute path");
+    }
+    let path = urlencoding::decode(path)?;
+    // `file:///C:/dir` is the Windows path `C:/dir`
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        return Ok(path[1..].to_string());
+    }
+    Ok(path.into_owned())
+}
+
+/// Returns the percent-decoded path of a constant request path, or `None` if
+/// there is nothing to decode. Encoded path separators are not decoded.
+fn percent_decode_path(path: &Pattern) -> Option<Pattern> {
+    let path = path.as_string()?;
+    if !path.contains('%') {
+        return None;
+    }
+    let lowercase = path.to_ascii_lowercase();
+    if lowercase.contains("%2f") || lowercase.contains("%5c") {
+        return None;
+    }
+    let decoded = urlencoding::decode(path).ok()?;
+    (decoded != path).then(|| Pattern::Constant(decoded.into()))
 }
 
 #[tracing::instrument(level = Level::TRACE, skip_all)]
@@ -2066,6 +2405,22 @@ async fn resolve_relative_request(
         return Ok(result);
     }
 
+    // A trailing slash requests a directory, e.g. `./utils/` must not resolve to
+    // `./utils.js` or to a file named `utils`.
+    let directory_only = path_pattern
+        .as_string()
+        .is_some_and(|path| path.ends_with('/'));
+
+    if let Some(overlay) = options_value.overlay {
+        if !directory_only {
+            if let Some(result) =
+                resolve_in_overlay(lookup_path, options_value, path_pattern, *overlay).await?
+            {
+                return Ok(result);
+            }
+        }
+    }
+
     let mut new_path = path_pattern.clone();
 
     let fragment_val = fragment.await?;
@@ -2077,7 +2432,7 @@ async fn resolve_relative_request(
         ));
     }
 
-    if !options_value.fully_specified {
+    if !options_value.fully_specified && !directory_only {
         // Add the extensions as alternatives to the path
         // read_matches keeps the order of alternatives intact
         new_path.push(Pattern::Alternatives(
@@ -2145,6 +2500,9 @@ async fn resolve_relative_request(
 
     for m in matches.iter() {
         if let PatternMatch::File(matched_pattern, path) = m {
+            if directory_only {
+                continue;
+            }
             let mut pushed = false;
             if !options_value.fully_specified {
                 for ext in options_value.extensions.iter() {
@@ -2243,6 +2601,37 @@ async fn resolve_relative_request(
     Ok(merge_results(results))
 }
 
+/// Looks up a constant relative request in the overlay, trying the extensions
+/// like for files on disk.
+async fn resolve_in_overlay(
+    lookup_path: Vc<FileSystemPath>,
+    options_value: &ResolveOptions,
+    path_pattern: &Pattern,
+    overlay: Vc<ResolveOverlay>,
+) -> Result<Option<Vc<ResolveResult>>> {
+    let Some(request) = path_pattern.as_string() else {
+        return Ok(None);
+    };
+    let overlay = overlay.await?;
+    let extensions = if options_value.fully_specified {
+        &[][..]
+    } else {
+        &options_value.extensions[..]
+    };
+    for ext in once("").chain(extensions.iter().map(|ext| &**ext)) {
+        let path = lookup_path
+            .join(format!("{request}{ext}").into())
+            .to_resolved()
+            .await?;
+        if let Some(source) = overlay.get(&path) {
+            return Ok(Some(
+                ResolveResult::source_with_key(RequestKey::new(request.into()), *source).cell(),
+            ));
+        }
+    }
+    Ok(None)
+}
+
 #[tracing::instrument(level = Level::TRACE, skip_all)]
 async fn apply_in_package(
     lookup_path: Vc<FileSystemPath>,
@@ -2699,6 +3088,11 @@ async fn resolved(
     fragment: Vc<RcStr>,
 ) -> Result<Vc<ResolveResult>> {
     let RealPathResult { path, symlinks } = &*fs_path.realpath_with_links().await?;
+    let path = if options_value.preserve_symlinks {
+        fs_path.to_resolved().await?
+    } else {
+        *path
+    };
 
     let path_ref = &*path.await?;
     // Check alias field for path aliases first
@@ -2717,7 +3111,7 @@ async fn resolved(
 
     if let Some(resolved_map) = options_value.resolved_map {
         let result = resolved_map
-            .lookup(**path, original_context, original_request)
+            .lookup(*path, original_context, original_request)
             .await?;
 
         let resolved_result = resolve_import_map_result(
@@ -2738,7 +3132,7 @@ async fn resolved(
     Ok(ResolveResult::source_with_affecting_sources(
         request_key,
         ResolvedVc::upcast(
-            FileSource::new_with_query(**path, query)
+            FileSource::new_with_query(*path, query)
                 .to_resolved()
                 .await?,
         ),
@@ -2776,6 +3170,31 @@ async fn handle_exports_imports_field(
         .map(AliasMatch::try_into_self)
         .collect::<Result<Vec<_>>>()?;
 
+    // Array entries are fallbacks: skip those whose targets don't exist.
+    let fallbacks = values
+        .iter()
+        .flat_map(|value| value.fallback_results())
+        .collect::<Vec<_>>();
+    let values = if fallbacks.is_empty() {
+        values
+    } else {
+        let mut unresolvable = HashSet::new();
+        for result_path in fallbacks {
+            let is_unresolvable =
+                match resolve_exports_imports_target(package_path, result_path, options).await? {
+                    Some(result) => result.await?.is_unresolvable_ref(),
+                    None => true,
+                };
+            if is_unresolvable {
+                unresolvable.insert(result_path);
+            }
+        }
+        values
+            .iter()
+            .map(|value| value.without_unresolvable_fallbacks(&unresolvable))
+            .collect()
+    };
+
     for value in values.iter() {
         if value.add_results(
             conditions,
@@ -2789,16 +3208,9 @@ async fn handle_exports_imports_field(
 
     let mut resolved_results = Vec::new();
     for (result_path, conditions) in results {
-        if let Some(result_path) = result_path.with_normalized_path() {
-            let request = Request::parse(Value::new(Pattern::Concatenation(vec![
-                Pattern::Constant("./".into()),
-                result_path,
-            ])))
-            .to_resolved()
-            .await?;
-
-            let resolve_result =
-                Box::pin(resolve_internal_inline(package_path, *request, options)).await?;
+        if let Some(resolve_result) =
+            resolve_exports_imports_target(package_path, result_path, options).await?
+        {
             if conditions.is_empty() {
                 resolved_results.push(resolve_result.with_request(path.into()));
             } else {
@@ -2818,6 +3230,28 @@ async fn handle_exports_imports_field(
     ))
 }
 
+/// Resolves a target of an `exports` or `imports` field relative to the
+/// package. Returns `None` if the target leaves the package.
+async fn resolve_exports_imports_target(
+    package_path: Vc<FileSystemPath>,
+    result_path: &Pattern,
+    options: Vc<ResolveOptions>,
+) -> Result<Option<Vc<ResolveResult>>> {
+    let Some(result_path) = result_path.with_normalized_path() else {
+        return Ok(None);
+    };
+    let request = Request::parse(Value::new(Pattern::Concatenation(vec![
+        Pattern::Constant("./".into()),
+        result_path,
+    ])))
+    .to_resolved()
+    .await?;
+
+    Ok(Some(
+        Box::pin(resolve_internal_inline(package_path, *request, options)).await?,
+    ))
+}
+
 /// Resolves a `#dep` import using the containing package.json's `imports`
 /// field. The dep may be a constant string or a pattern, and the values can be
 /// static strings or conditions like `import` or `require` to handle ESM/CJS
@@ -3123,3 +3557,29 @@ impl ValueToString for ModulePart {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::file_url_to_path;
+
+    #[test]
+    fn file_url_to_path_supports_local_hosts() {
+        assert_eq!(file_url_to_path("///abs/path.js").unwrap(), "/abs/path.js");
+        assert_eq!(
+            file_url_to_path("//localhost/abs/path.js").unwrap(),
+            "/abs/path.js"
+        );
+        assert_eq!(file_url_to_path("/abs/path.js").unwrap(), "/abs/path.js");
+        assert_eq!(
+            file_url_to_path("///abs/a%20b.js?query#fragment").unwrap(),
+            "/abs/a b.js"
+        );
+        assert_eq!(file_url_to_path("///C:/dir/a.js").unwrap(), "C:/dir/a.js");
+    }
+
+    #[test]
+    fn file_url_to_path_rejects_remote_hosts() {
+        assert!(file_url_to_path("//example.com/abs/path.js").is_err());
+        assert!(file_url_to_path("relative/path.js").is_err());
+    }
+}
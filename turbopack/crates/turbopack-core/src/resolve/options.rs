@@ -4,8 +4,8 @@ use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
 use turbo_rcstr::RcStr;
 use turbo_tasks::{
-    debug::ValueDebugFormat, trace::TraceRawVcs, FxIndexSet, ResolvedVc, TryJoinIterExt, Value,
-    ValueToString, Vc,
+    debug::ValueDebugFormat, trace::TraceRawVcs, FxIndexMap, FxIndexSet, ResolvedVc,
+    TryJoinIterExt, Value, ValueToString, Vc,
 };
 use turbo_tasks_fs::{glob::Glob, FileSystemPath};
 
@@ -15,7 +15,10 @@ use super::{
     plugin::BeforeResolvePlugin,
     AliasPattern, ExternalType, ResolveResult, ResolveResultItem,
 };
-use crate::resolve::{parse::Request, plugin::AfterResolvePlugin, ExternalTraced};
+use crate::{
+    resolve::{parse::Request, plugin::AfterResolvePlugin, ExternalTraced},
+    source::Source,
+};
 
 #[turbo_tasks::value(shared)]
 #[derive(Hash, Debug)]
@@ -38,6 +41,26 @@ pub enum ResolveModules {
         dir: ResolvedVc<FileSystemPath>,
         excluded_extensions: ResolvedVc<ExcludedExtensions>,
     },
+    /// look up packages in the Yarn Plug'n'Play manifest in that directory,
+    /// if there is one. The manifest is read from `.pnp.data.json` or the
+    /// data inlined into `.pnp.cjs`. Packages stored in zip archives (like
+    /// the ones in `.yarn/cache`) are not supported and reported as issues,
+    /// they need to be unplugged.
+    Pnp(ResolvedVc<FileSystemPath>),
+}
+
+/// Pins the copy of a package that a bare specifier resolves to, like yarn
+/// `resolutions` or npm `overrides` in a root package.json.
+#[derive(
+    TraceRawVcs, Hash, PartialEq, Eq, Clone, Debug, Serialize, Deserialize, ValueDebugFormat,
+)]
+pub enum PackageOverride {
+    /// use the package in that directory instead of looking it up
+    Path(ResolvedVc<FileSystemPath>),
+    /// use the nearest copy of the package whose version satisfies that semver
+    /// range, or the nearest copy if none does. An invalid range is reported
+    /// as an issue and ignored.
+    Version(RcStr),
 }
 
 #[derive(TraceRawVcs, Hash, PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
@@ -372,6 +395,12 @@ pub struct ResolvedMap {
     )>,
 }
 
+/// Sources which resolve as if they were files at the given paths, e.g.
+/// generated code that is never written to disk. An entry shadows a file on
+/// disk at the same path.
+#[turbo_tasks::value(transparent)]
+pub struct ResolveOverlay(FxIndexMap<ResolvedVc<FileSystemPath>, ResolvedVc<Box<dyn Source>>>);
+
 #[turbo_tasks::value(shared)]
 #[derive(Clone)]
 pub enum ImportMapResult {
@@ -573,8 +602,14 @@ pub struct ResolveOptions {
     pub prefer_relative: bool,
     /// The extensions that should be added to a request when resolving.
     pub extensions: Vec<RcStr>,
+    /// The extensions used instead of `extensions` for imports with a `type`
+    /// attribute, by attribute value. Those imports don't use any extensions
+    /// unless their type is listed here.
+    pub import_type_extensions: Vec<(RcStr, Vec<RcStr>)>,
     /// The locations where to resolve modules.
     pub modules: Vec<ResolveModules>,
+    /// Packages which resolve to a specific copy, by package name.
+    pub package_overrides: Vec<(RcStr, PackageOverride)>,
     /// How to resolve packages.
     pub into_package: Vec<ResolveIntoPackage>,
     /// How to resolve in packages.
@@ -586,12 +621,23 @@ pub struct ResolveOptions {
     /// An import map to use when a request is otherwise unresolvable.
     pub fallback_import_map: Option<ResolvedVc<ImportMap>>,
     pub resolved_map: Option<ResolvedVc<ResolvedMap>>,
+    /// Sources which are consulted before the file system when resolving
+    /// relative requests.
+    pub overlay: Option<ResolvedVc<ResolveOverlay>>,
     pub before_resolve_plugins: Vec<ResolvedVc<Box<dyn BeforeResolvePlugin>>>,
+    /// Plugins which are consulted in order when a request could not be
+    /// resolved otherwise, after the `fallback_import_map`.
+    pub fallback_resolve_plugins: Vec<ResolvedVc<Box<dyn BeforeResolvePlugin>>>,
     pub plugins: Vec<ResolvedVc<Box<dyn AfterResolvePlugin>>>,
     /// Support resolving *.js requests to *.ts files
     pub enable_typescript_with_output_extension: bool,
     /// Warn instead of error for resolve errors
     pub loose_errors: bool,
+    /// Keep the path through which a file or package was found instead of
+    /// resolving symlinks to the real path, like Node.js'
+    /// `--preserve-symlinks`. A file reached through different symlinks
+    /// becomes a separate module for each of them.
+    pub preserve_symlinks: bool,
 
     pub placeholder_for_future_extensions: (),
 }
@@ -664,7 +710,9 @@ impl ResolveOptions {
 #[derive(Hash, Clone, Debug)]
 pub struct ResolveModulesOptions {
     pub modules: Vec<ResolveModules>,
+    pub package_overrides: Vec<(RcStr, PackageOverride)>,
     pub extensions: Vec<RcStr>,
+    pub preserve_symlinks: bool,
 }
 
 #[turbo_tasks::function]
@@ -674,7 +722,9 @@ pub async fn resolve_modules_options(
     let options = options.await?;
     Ok(ResolveModulesOptions {
         modules: options.modules.clone(),
+        package_overrides: options.package_overrides.clone(),
         extensions: options.extensions.clone(),
+        preserve_symlinks: options.preserve_symlinks,
     }
     .into())
 }
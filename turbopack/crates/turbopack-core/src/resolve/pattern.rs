@@ -104,6 +104,94 @@ fn longest_common_suffix<'a>(strings: &[&'a str]) -> &'a str {
     &first[(first.len() - len)..]
 }
 
+/// Returns the alternatives of a normalized pattern, each as the list of its
+/// constant and dynamic parts.
+fn alternative_parts(pattern: &Pattern) -> Vec<&[Pattern]> {
+    let alternatives = match pattern {
+        Pattern::Alternatives(list) => &list[..],
+        _ => std::slice::from_ref(pattern),
+    };
+    alternatives
+        .iter()
+        .map(|alternative| match alternative {
+            Pattern::Concatenation(parts) => &parts[..],
+            _ => std::slice::from_ref(alternative),
+        })
+        .collect()
+}
+
+/// Intersects two lists of constant and dynamic parts, as returned by
+/// [`alternative_parts`]. Each item of the result is a list of parts that
+/// matches a subset of the intersection, and together they match all of it.
+fn intersect_parts(a: &[Pattern], b: &[Pattern]) -> Vec<Vec<Pattern>> {
+    fn prepend(part: Pattern, results: Vec<Vec<Pattern>>) -> Vec<Vec<Pattern>> {
+        results
+            .into_iter()
+            .map(|mut parts| {
+                parts.insert(0, part.clone());
+                parts
+            })
+            .collect()
+    }
+
+    /// Returns `rest` after the first `len` bytes of the constant `c`.
+    fn skip_constant(c: &str, len: usize, rest: &[Pattern]) -> Vec<Pattern> {
+        let mut parts = Vec::with_capacity(rest.len() + 1);
+        if len < c.len() {
+            parts.push(Pattern::Constant(c[len..].into()));
+        }
+        parts.extend(rest.iter().cloned());
+        parts
+    }
+
+    match (a.first(), b.first()) {
+        (Some(Pattern::Constant(c)), _) if c.is_empty() => intersect_parts(&a[1..], b),
+        (_, Some(Pattern::Constant(c))) if c.is_empty() => intersect_parts(a, &b[1..]),
+        (None, None) => vec![Vec::new()],
+        (None, Some(_)) | (Some(_), None) => {
+            // Only dynamic parts can match the empty rest.
+            if a.iter().chain(b).all(|part| *part == Pattern::Dynamic) {
+                vec![Vec::new()]
+            } else {
+                Vec::new()
+            }
+        }
+        (Some(Pattern::Constant(x)), Some(Pattern::Constant(y))) => {
+            let len = x.len().min(y.len());
+            if x.as_bytes()[..len] != y.as_bytes()[..len] {
+                return Vec::new();
+            }
+            prepend(
+                Pattern::Constant(x[..len].into()),
+                intersect_parts(
+                    &skip_constant(x, len, &a[1..]),
+                    &skip_constant(y, len, &b[1..]),
+                ),
+            )
+        }
+        (Some(Pattern::Dynamic), Some(Pattern::Constant(y))) => {
+            // The dynamic part either ends here or also matches the first
+            // character of the constant.
+            let mut results = intersect_parts(&a[1..], b);
+            let len = y.chars().next().unwrap().len_utf8();
+            results.extend(prepend(
+                Pattern::Constant(y[..len].into()),
+                intersect_parts(a, &skip_constant(y, len, &b[1..])),
+            ));
+            results
+        }
+        (Some(Pattern::Constant(_)), Some(Pattern::Dynamic)) => intersect_parts(b, a),
+        (Some(Pattern::Dynamic), Some(Pattern::Dynamic)) => {
+            // The dynamic part that ends first is followed by the remaining
+            // parts of its alternative, while the other one still continues.
+            let mut results = intersect_parts(&a[1..], b);
+            results.extend(intersect_parts(a, &b[1..]));
+            prepend(Pattern::Dynamic, results)
+        }
+        _ => panic!("for intersecting a Pattern must be normalized"),
+    }
+}
+
 impl Pattern {
     // TODO this should be removed in favor of pattern resolving
     pub fn into_string(self) -> Option<RcStr> {
@@ -727,6 +815,67 @@ impl Pattern {
         }
     }
 
+    /// Returns a pattern that matches the values that are matched by both
+    /// `self` and `other`, or `None` if no value can be matched by both.
+    ///
+    /// Dynamic parts are treated as matching any value. The restrictions that
+    /// [`Pattern::is_match`] applies to dynamic parts, e.g. for
+    /// `node_modules`, still apply when matching against the result.
+    pub fn intersection(&self, other: &Pattern) -> Option<Pattern> {
+        let mut a = self.clone();
+        a.normalize();
+        let mut b = other.clone();
+        b.normalize();
+
+        let mut alternatives: Vec<Pattern> = Vec::new();
+        for a in alternative_parts(&a) {
+            for b in alternative_parts(&b) {
+                for parts in intersect_parts(a, b) {
+                    let mut alternative = if parts.is_empty() {
+                        Pattern::Constant(RcStr::default())
+                    } else {
+                        Pattern::Concatenation(parts)
+                    };
+                    alternative.normalize();
+                    if !alternatives.contains(&alternative) {
+                        alternatives.push(alternative);
+                    }
+                }
+            }
+        }
+        match alternatives.len() {
+            0 => None,
+            1 => alternatives.pop(),
+            _ => Some(Pattern::Alternatives(alternatives)),
+        }
+    }
+
+    /// Returns a pattern that matches the values that are matched by `self`
+    /// but not by `other`, or `None` if `other` matches everything `self`
+    /// matches.
+    ///
+    /// Patterns can't express exclusions, so this only removes the
+    /// alternatives of `self` that `other` fully covers. Alternatives that are
+    /// only partially covered are kept, so values still need to be checked
+    /// against `other` with [`Pattern::is_match`].
+    pub fn difference(&self, other: &Pattern) -> Option<Pattern> {
+        let mut pattern = self.clone();
+        pattern.normalize();
+        let alternatives = match pattern {
+            Pattern::Alternatives(list) => list,
+            pattern => vec![pattern],
+        };
+        let mut remaining = alternatives
+            .into_iter()
+            .filter(|alternative| alternative.intersection(other).as_ref() != Some(alternative))
+            .collect::<Vec<_>>();
+        match remaining.len() {
+            0 => None,
+            1 => remaining.pop(),
+            _ => Some(Pattern::Alternatives(remaining)),
+        }
+    }
+
     pub fn is_match(&self, value: &str) -> bool {
         if let Pattern::Alternatives(list) = self {
             list.iter()
@@ -2300,4 +2449,108 @@ mod tests {
             Some("@/sub/file1"),
         );
     }
+
+    #[test]
+    fn intersection() {
+        let src =
+            Pattern::Concatenation(vec![Pattern::Constant("./src/".into()), Pattern::Dynamic]);
+        let js = Pattern::Concatenation(vec![Pattern::Dynamic, Pattern::Constant(".js".into())]);
+        let src_js = Pattern::Concatenation(vec![
+            Pattern::Constant("./src/".into()),
+            Pattern::Dynamic,
+            Pattern::Constant(".js".into()),
+        ]);
+
+        // overlapping
+        assert_eq!(src.intersection(&js), Some(src_js.clone()));
+        assert_eq!(js.intersection(&src), Some(src_js.clone()));
+        assert_eq!(Pattern::Dynamic.intersection(&js), Some(js.clone()));
+        assert_eq!(
+            Pattern::Constant("./src/index.js".into()).intersection(&src_js),
+            Some(Pattern::Constant("./src/index.js".into()))
+        );
+        assert_eq!(
+            Pattern::Alternatives(vec![
+                src.clone(),
+                Pattern::Constant("./lib/index.js".into()),
+                Pattern::Constant("./src/index.css".into()),
+            ])
+            .intersection(&src_js),
+            Some(src_js.clone())
+        );
+
+        // both orders of the constants are kept
+        let a = Pattern::Concatenation(vec![
+            Pattern::Dynamic,
+            Pattern::Constant("/a/".into()),
+            Pattern::Dynamic,
+        ]);
+        let b = Pattern::Concatenation(vec![
+            Pattern::Dynamic,
+            Pattern::Constant("/b/".into()),
+            Pattern::Dynamic,
+        ]);
+        let a_and_b = a.intersection(&b).unwrap();
+        assert!(a_and_b.is_match("x/a/y/b/z"));
+        assert!(a_and_b.is_match("x/b/y/a/z"));
+        assert!(a_and_b.is_match("x/a/b/z"));
+        assert!(!a_and_b.is_match("x/a/y"));
+
+        // disjoint
+        assert_eq!(
+            Pattern::Constant("./a.js".into()).intersection(&Pattern::Constant("./b.js".into())),
+            None
+        );
+        assert_eq!(
+            src.intersection(&Pattern::Concatenation(vec![
+                Pattern::Constant("./lib/".into()),
+                Pattern::Dynamic
+            ])),
+            None
+        );
+        assert_eq!(
+            src_js.intersection(&Pattern::Concatenation(vec![
+                Pattern::Dynamic,
+                Pattern::Constant(".css".into())
+            ])),
+            None
+        );
+    }
+
+    #[test]
+    fn difference() {
+        let tests =
+            Pattern::Concatenation(vec![Pattern::Dynamic, Pattern::Constant(".test.js".into())]);
+        let lib =
+            Pattern::Concatenation(vec![Pattern::Constant("./lib/".into()), Pattern::Dynamic]);
+        let files = Pattern::Alternatives(vec![
+            Pattern::Constant("./a.js".into()),
+            Pattern::Constant("./a.test.js".into()),
+            lib.clone(),
+        ]);
+
+        // Fully covered alternatives are removed, partially covered ones are
+        // kept.
+        assert_eq!(
+            files.difference(&tests),
+            Some(Pattern::Alternatives(vec![
+                Pattern::Constant("./a.js".into()),
+                lib.clone(),
+            ]))
+        );
+        assert_eq!(
+            lib.difference(&Pattern::Concatenation(vec![
+                Pattern::Constant("./".into()),
+                Pattern::Dynamic
+            ])),
+            None
+        );
+        assert_eq!(files.difference(&Pattern::Dynamic), None);
+
+        // disjoint
+        assert_eq!(
+            lib.difference(&Pattern::Constant("./a.js".into())),
+            Some(lib.clone())
+        );
+    }
 }
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{debug::ValueDebugFormat, trace::TraceRawVcs, FxIndexMap, ResolvedVc, Vc};
+use turbo_tasks_fs::{FileContent, FileJsonContent, FileSystemPath};
+
+use crate::issue::{Issue, IssueSeverity, IssueStage, OptionStyledString, StyledString};
+
+/// The file Yarn writes the Plug'n'Play data to when `pnpEnableInlining` is
+/// disabled.
+pub const PNP_DATA_FILE: &str = ".pnp.data.json";
+
+/// The Plug'n'Play runtime, which has the data inlined by default.
+pub const PNP_RUNTIME_FILE: &str = ".pnp.cjs";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPnpData {
+    package_registry_data: Vec<(Option<RcStr>, Vec<(Option<RcStr>, RawPackageInformation)>)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPackageInformation {
+    package_location: RcStr,
+    #[serde(default)]
+    package_dependencies: Vec<(RcStr, Option<RawDependencyTarget>)>,
+}
+
+/// A dependency is either a reference into the package of the same name, or an
+/// alias to a `[name, reference]` pair of another package.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDependencyTarget {
+    Reference(RcStr),
+    Alias(RcStr, RcStr),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct PnpPackage {
+    /// The location of the package relative to the manifest directory,
+    /// without leading `./` or trailing `/`.
+    pub location: RcStr,
+    /// The location of each dependency, or `None` for an unmet peer dependency.
+    pub dependencies: FxIndexMap<RcStr, Option<RcStr>>,
+}
+
+/// The package registry of a Yarn Plug'n'Play manifest.
+///
+/// Packages stored in `.yarn/cache` zip archives can't be read from the file
+/// system, so only unplugged packages and workspaces resolve in practice.
+#[turbo_tasks::value(shared)]
+pub struct PnpManifest {
+    pub packages: Vec<PnpPackage>,
+}
+
+/// A [PnpManifest] and the file it was read from.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, ValueDebugFormat,
+)]
+pub struct PnpManifestFile {
+    /// Either [PNP_DATA_FILE] or [PNP_RUNTIME_FILE].
+    pub path: ResolvedVc<FileSystemPath>,
+    pub manifest: ResolvedVc<PnpManifest>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionPnpManifest(Option<PnpManifestFile>);
+
+impl PnpManifest {
+    /// Returns the location of the package `package_name` as a dependency of
+    /// the innermost package containing `issuer`. Both paths are relative to
+    /// the manifest directory.
+    pub fn resolve_package(&self, issuer: &str, package_name: &str) -> Option<&RcStr> {
+        self.packages
+            .iter()
+            .filter(|package| is_inside(issuer, &package.location))
+            .max_by_key(|package| package.location.len())?
+            .dependencies
+            .get(package_name)?
+            .as_ref()
+    }
+}
+
+fn is_inside(path: &str, location: &str) -> bool {
+    location.is_empty()
+        || path
+            .strip_prefix(location)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Returns the zip archive containing `location`, if any.
+pub fn zip_archive(location: &str) -> Option<&str> {
+    location
+        .split('/')
+        .scan(0, |end, segment| {
+            *end += segment.len() + 1;
+            Some((*end - 1, segment))
+        })
+        .find(|(_, segment)| segment.ends_with(".zip"))
+        .map(|(end, _)| &location[..end])
+}
+
+fn normalize_location(location: &str) -> RcStr {
+    let location = location.strip_prefix("./").unwrap_or(location);
+    location.trim_end_matches('/').into()
+}
+
+impl TryFrom<&serde_json::Value> for PnpManifest {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
+        let data = RawPnpData::deserialize(value)?;
+
+        let mut locations: HashMap<(Option<&str>, &str), RcStr> = HashMap::new();
+        for (name, references) in &data.package_registry_data {
+            for (reference, information) in references {
+                if let Some(reference) = reference {
+                    locations.insert(
+                        (name.as_deref(), &**reference),
+                        normalize_location(&information.package_location),
+                    );
+                }
+            }
+        }
+
+        let packages = data
+            .package_registry_data
+            .iter()
+            .flat_map(|(_, references)| references)
+            .map(|(_, information)| PnpPackage {
+                location: normalize_location(&information.package_location),
+                dependencies: information
+                    .package_dependencies
+                    .iter()
+                    .map(|(name, target)| {
+                        let location = target.as_ref().and_then(|target| match target {
+                            RawDependencyTarget::Reference(reference) => {
+                                locations.get(&(Some(&**name), &**reference))
+                            }
+                            RawDependencyTarget::Alias(name, reference) => {
+                                locations.get(&(Some(&**name), &**reference))
+                            }
+                        });
+                        (name.clone(), location.cloned())
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(PnpManifest { packages })
+    }
+}
+
+/// Extracts the data Yarn inlines into [PNP_RUNTIME_FILE] as the
+/// `RAW_RUNTIME_STATE` string literal.
+fn inlined_pnp_data(runtime: &str) -> Option<String> {
+    let (_, rest) = runtime.split_once("const RAW_RUNTIME_STATE =")?;
+    let mut chars = rest.trim_start().strip_prefix('\'')?.chars();
+    let mut data = String::new();
+    loop {
+        match chars.next()? {
+            '\'' => return Some(data),
+            '\\' => match chars.next()? {
+                // A line continuation
+                '\n' => {}
+                c => data.push(c),
+            },
+            c => data.push(c),
+        }
+    }
+}
+
+async fn pnp_manifest_file(
+    path: Vc<FileSystemPath>,
+    json: &serde_json::Value,
+) -> Result<Vc<OptionPnpManifest>> {
+    let manifest =
+        PnpManifest::try_from(json).context("failed to read the Plug'n'Play package registry")?;
+    Ok(Vc::cell(Some(PnpManifestFile {
+        path: path.to_resolved().await?,
+        manifest: manifest.resolved_cell(),
+    })))
+}
+
+/// Reads the Plug'n'Play manifest in `dir`, if there is one. The data is read
+/// from [PNP_DATA_FILE] or, if that doesn't exist, from [PNP_RUNTIME_FILE].
+#[turbo_tasks::function]
+pub async fn read_pnp_manifest(dir: Vc<FileSystemPath>) -> Result<Vc<OptionPnpManifest>> {
+    let data_path = dir.join(PNP_DATA_FILE.into());
+    if let FileJsonContent::Content(json) = &*data_path.read_json().await? {
+        return pnp_manifest_file(data_path, json).await;
+    }
+
+    let runtime_path = dir.join(PNP_RUNTIME_FILE.into());
+    let FileContent::Content(file) = &*runtime_path.read().await? else {
+        return Ok(Vc::cell(None));
+    };
+    let Some(data) = inlined_pnp_data(&file.content().to_str()?) else {
+        return Ok(Vc::cell(None));
+    };
+    let json = serde_json::from_str(&data)
+        .with_context(|| format!("failed to parse the data inlined into {PNP_RUNTIME_FILE}"))?;
+    pnp_manifest_file(runtime_path, &json).await
+}
+
+/// A package which Yarn stores in a zip archive. Those can't be read, so the
+/// package doesn't resolve.
+#[turbo_tasks::value(shared)]
+pub struct PnpZipArchiveIssue {
+    pub manifest_path: ResolvedVc<FileSystemPath>,
+    pub package_name: RcStr,
+    pub archive: RcStr,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for PnpZipArchiveIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Strong("Package stored in a zip archive: ".into()),
+            StyledString::Code(self.package_name.clone()),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.into()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        *self.manifest_path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
+            StyledString::Text(
+                format!(
+                    "Yarn Plug'n'Play stores \"{}\" in \"{}\". Packages in zip archives (like the \
+                     ones in .yarn/cache) are not supported. Run `yarn unplug {}` or set \
+                     `nodeLinker: node-modules` in .yarnrc.yml to make it resolvable.",
+                    self.package_name, self.archive, self.package_name
+                )
+                .into(),
+            )
+            .resolved_cell(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{inlined_pnp_data, zip_archive, PnpManifest};
+
+    #[test]
+    fn resolve_package() {
+        let manifest = PnpManifest::try_from(&json!({
+            "packageRegistryData": [
+                [null, [
+                    [null, {
+                        "packageLocation": "./",
+                        "packageDependencies": [["app", "workspace:."]]
+                    }]
+                ]],
+                ["app", [
+                    ["workspace:.", {
+                        "packageLocation": "./",
+                        "packageDependencies": [
+                            ["a", "npm:1.0.0"],
+                            ["b", ["a", "npm:2.0.0"]],
+                            ["peer", null]
+                        ]
+                    }]
+                ]],
+                ["a", [
+                    ["npm:1.0.0", {
+                        "packageLocation": "./.yarn/unplugged/a-npm-1.0.0/node_modules/a/",
+                        "packageDependencies": [["a", "npm:2.0.0"]]
+                    }],
+                    ["npm:2.0.0", {
+                        "packageLocation": "./.yarn/cache/a-npm-2.0.0.zip/node_modules/a/",
+                        "packageDependencies": []
+                    }]
+                ]]
+            ]
+        }))
+        .unwrap();
+
+        let a1 = ".yarn/unplugged/a-npm-1.0.0/node_modules/a";
+        let a2 = ".yarn/cache/a-npm-2.0.0.zip/node_modules/a";
+
+        assert_eq!(manifest.resolve_package("src", "a").unwrap(), a1);
+        assert_eq!(manifest.resolve_package("", "b").unwrap(), a2);
+        assert_eq!(manifest.resolve_package("src", "peer"), None);
+        assert_eq!(manifest.resolve_package("src", "missing"), None);
+        assert_eq!(
+            manifest.resolve_package(&format!("{a1}/lib"), "a").unwrap(),
+            a2
+        );
+    }
+
+    #[test]
+    fn inlined_data() {
+        let runtime = concat!(
+            "#!/usr/bin/env node\n",
+            "/* eslint-disable */\n",
+            "\"use strict\";\n",
+            "\n",
+            "const RAW_RUNTIME_STATE =\n",
+            "'{\\\n",
+            "  \"packageRegistryData\": [[\"it\\'s\", [[\"a\\\\\\\\b\", {}]]]]\\\n",
+            "}';\n",
+            "\n",
+            "function $$SETUP_STATE(hydrateRuntimeState, basePath) {}\n",
+        );
+        assert_eq!(
+            inlined_pnp_data(runtime).unwrap(),
+            "{  \"packageRegistryData\": [[\"it's\", [[\"a\\\\b\", {}]]]]}"
+        );
+        assert_eq!(inlined_pnp_data("module.exports = {};"), None);
+    }
+
+    #[test]
+    fn zip_archives() {
+        assert_eq!(
+            zip_archive(".yarn/cache/a-npm-2.0.0.zip/node_modules/a"),
+            Some(".yarn/cache/a-npm-2.0.0.zip")
+        );
+        assert_eq!(
+            zip_archive(".yarn/unplugged/a-npm-1.0.0/node_modules/a"),
+            None
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Display,
     ops::Deref,
 };
@@ -234,6 +234,76 @@ impl SubpathValue {
 }
 
 impl ReplacedSubpathValue {
+    /// Returns the results of all array entries which are followed by another
+    /// entry, i.e. the results that can fall back to an alternative.
+    pub fn fallback_results(&self) -> Vec<&Pattern> {
+        let mut results = Vec::new();
+        self.add_fallback_results(false, &mut results);
+        results
+    }
+
+    fn add_fallback_results<'a>(&'a self, has_fallback: bool, results: &mut Vec<&'a Pattern>) {
+        match self {
+            ReplacedSubpathValue::Alternatives(list) => {
+                for (i, value) in list.iter().enumerate() {
+                    value.add_fallback_results(has_fallback || i + 1 < list.len(), results);
+                }
+            }
+            ReplacedSubpathValue::Conditional(list) => {
+                for (_, value) in list {
+                    value.add_fallback_results(has_fallback, results);
+                }
+            }
+            ReplacedSubpathValue::Result(r) => {
+                if has_fallback {
+                    results.push(r);
+                }
+            }
+            ReplacedSubpathValue::Excluded => {}
+        }
+    }
+
+    /// Removes array entries whose results are all `unresolvable`, so that the
+    /// next entry is used instead. The last entry of an array is always kept.
+    pub fn without_unresolvable_fallbacks(&self, unresolvable: &HashSet<&Pattern>) -> Self {
+        match self {
+            ReplacedSubpathValue::Alternatives(list) => {
+                let mut list = list
+                    .iter()
+                    .map(|value| value.without_unresolvable_fallbacks(unresolvable))
+                    .collect::<Vec<_>>();
+                while list.len() > 1 && list[0].is_unresolvable(unresolvable) {
+                    list.remove(0);
+                }
+                ReplacedSubpathValue::Alternatives(list)
+            }
+            ReplacedSubpathValue::Conditional(list) => ReplacedSubpathValue::Conditional(
+                list.iter()
+                    .map(|(condition, value)| {
+                        (
+                            condition.clone(),
+                            value.without_unresolvable_fallbacks(unresolvable),
+                        )
+                    })
+                    .collect(),
+            ),
+            ReplacedSubpathValue::Result(_) | ReplacedSubpathValue::Excluded => self.clone(),
+        }
+    }
+
+    fn is_unresolvable(&self, unresolvable: &HashSet<&Pattern>) -> bool {
+        match self {
+            ReplacedSubpathValue::Alternatives(list) => {
+                list.iter().all(|value| value.is_unresolvable(unresolvable))
+            }
+            ReplacedSubpathValue::Conditional(list) => list
+                .iter()
+                .all(|(_, value)| value.is_unresolvable(unresolvable)),
+            ReplacedSubpathValue::Result(r) => unresolvable.contains(r),
+            ReplacedSubpathValue::Excluded => false,
+        }
+    }
+
     /// Walks the [ReplacedSubpathValue] and adds results to the `target`
     /// vector. It uses the `conditions` to skip or enter conditional
     /// results. The state of conditions is stored within
@@ -0,0 +1,93 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use anyhow::{bail, Result};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{ResolvedVc, ValueToString, Vc};
+use turbo_tasks_fs::{File, FileSystem, VirtualFileSystem};
+use turbo_tasks_testing::{register, run, Registration};
+use turbopack_core::{
+    asset::AssetContent,
+    module::Module,
+    raw_module::RawModule,
+    reference::{ModuleReference, ModuleReferences},
+    resolve::ModuleResolveResult,
+    virtual_source::VirtualSource,
+};
+
+static REGISTRATION: Registration = register!(turbopack_core::register);
+
+/// A reference to `module`. Without a module, both resolving and describing
+/// the reference fail.
+#[turbo_tasks::value]
+struct TestReference {
+    module: Option<ResolvedVc<Box<dyn Module>>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for TestReference {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Result<Vc<RcStr>> {
+        if self.module.is_none() {
+            bail!("the reference can't be described");
+        }
+        Ok(Vc::cell("test reference".into()))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ModuleReference for TestReference {
+    #[turbo_tasks::function]
+    fn resolve_reference(&self) -> Result<Vc<ModuleResolveResult>> {
+        let Some(module) = self.module else {
+            bail!("the reference can't be resolved");
+        };
+        Ok(ModuleResolveResult::module(module).cell())
+    }
+}
+
+async fn references() -> Result<(ResolvedVc<Box<dyn Module>>, Vc<ModuleReferences>)> {
+    let source = VirtualSource::new(
+        VirtualFileSystem::new().root().join("index.js".into()),
+        AssetContent::file(File::from("").into()),
+    );
+    let module = ResolvedVc::upcast(RawModule::new(Vc::upcast(source)).to_resolved().await?);
+    let references = [Some(module), None, Some(module)]
+        .into_iter()
+        .map(|module| ResolvedVc::upcast(TestReference { module }.resolved_cell()))
+        .collect();
+    Ok((module, Vc::cell(references)))
+}
+
+#[tokio::test]
+async fn resolve_all_continues_after_a_failure() {
+    run(&REGISTRATION, || async {
+        let (module, references) = references().await?;
+
+        let resolved = references.resolve_all(false).await?;
+        assert_eq!(resolved.len(), 3);
+        for (i, &(_, result)) in resolved.iter().enumerate() {
+            if i == 1 {
+                assert!(result.await?.is_unresolvable_ref());
+            } else {
+                assert_eq!(*result.primary_modules().await?, [module]);
+            }
+        }
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn resolve_all_fails_fast() {
+    run(&REGISTRATION, || async {
+        let (_, references) = references().await?;
+
+        assert!(references.resolve_all(true).await.is_err());
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
@@ -237,6 +237,22 @@ impl Effect {
             Effect::Unreachable { .. } => {}
         }
     }
+
+    /// Returns the span of the code that caused this effect, or `None` for
+    /// [Effect::Unreachable].
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Effect::Conditional { span, .. }
+            | Effect::Call { span, .. }
+            | Effect::MemberCall { span, .. }
+            | Effect::Member { span, .. }
+            | Effect::ImportedBinding { span, .. }
+            | Effect::FreeVar { span, .. }
+            | Effect::TypeOf { span, .. }
+            | Effect::ImportMeta { span, .. } => Some(*span),
+            Effect::Unreachable { .. } => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -300,6 +316,10 @@ impl EvalContext {
     /// Produce a new [EvalContext] from a [Program]. If you wish to support
     /// webpackIgnore or turbopackIgnore comments, you must pass those in,
     /// since the AST does not include comments by default.
+    ///
+    /// All imports of the module are collected here, before the graph is
+    /// created, so imported bindings resolve like hoisted declarations even
+    /// when they are used before the `import` statement.
     pub fn new(
         module: &Program,
         unresolved_mark: Mark,
@@ -1346,22 +1366,32 @@ impl VisitAstPath for Analyzer<'_> {
         n: &'ast CallExpr,
         ast_path: &mut AstNodePath<AstParentNodeRef<'r>>,
     ) {
-        // We handle `define(function (require) {})` here.
+        // We handle `define(function (require) {})` and UMD factories here.
         if let Callee::Expr(callee) = &n.callee {
-            if n.args.len() == 1 {
-                if let Some(require_var_id) = extract_var_from_umd_factory(callee, &n.args) {
-                    self.add_value(
-                        require_var_id,
+            if let Some(factory) = extract_umd_factory(callee, &n.args) {
+                let ignore = self
+                    .eval_context
+                    .imports
+                    .get_attributes(n.callee.span())
+                    .ignore;
+                for (param, name) in factory.params.iter().zip(["require", "exports", "module"]) {
+                    let Pat::Ident(param) = &param.pat else {
+                        break;
+                    };
+                    if &*param.id.sym != name {
+                        break;
+                    }
+                    let value = if name == "require" {
                         JsValue::unknown_if(
-                            self.eval_context
-                                .imports
-                                .get_attributes(n.callee.span())
-                                .ignore,
+                            ignore,
                             JsValue::WellKnownFunction(WellKnownFunctionKind::Require),
                             true,
                             "ignored require",
-                        ),
-                    );
+                        )
+                    } else {
+                        JsValue::FreeVar(param.id.sym.clone())
+                    };
+                    self.add_value(param.to_id(), value);
                 }
             }
         }
@@ -2268,45 +2298,47 @@ impl Analyzer<'_> {
     }
 }
 
-fn extract_var_from_umd_factory(callee: &Expr, args: &[ExprOrSpread]) -> Option<Id> {
+/// Returns the factory function of `define(function (require) {})` or of a UMD
+/// wrapper like `(function (root, factory) {})(this, function (require, exports,
+/// module) {})`.
+///
+/// The `require`, `exports` and `module` parameters of the factory (in that
+/// order) can be treated as the well-known values.
+fn extract_umd_factory<'a>(callee: &Expr, args: &'a [ExprOrSpread]) -> Option<&'a Function> {
+    fn is_factory(function: &Function) -> bool {
+        matches!(
+            function.params.first().map(|param| &param.pat),
+            Some(Pat::Ident(param)) if &*param.id.sym == "require"
+        )
+    }
+
     match unparen(callee) {
         Expr::Ident(Ident { sym, .. }) => {
-            if &**sym == "define" {
+            if &**sym == "define" && args.len() == 1 {
                 if let Expr::Fn(FnExpr { function, .. }) = &*args[0].expr {
-                    let params = &*function.params;
-                    if params.len() == 1 {
-                        if let Pat::Ident(param) = &params[0].pat {
-                            if &*param.id.sym == "require" {
-                                return Some(param.to_id());
-                            }
-                        }
+                    if function.params.len() == 1 && is_factory(function) {
+                        return Some(function);
                     }
                 }
             }
         }
 
-        // umd may use (function (factory){
+        // umd may use (function (root, factory){
         //   // Somewhere, define(['require', 'exports'], factory)
-        // }(function (require, exports){}))
+        //   // or factory(require, exports, module)
+        // }(this, function (require, exports, module){}))
         //
         // In all module system which has `require`, `require` in the factory function can be
         // treated as a well-known require.
-        Expr::Fn(FnExpr { function, .. }) => {
-            let params = &*function.params;
-            if params.len() == 1 {
-                if let Some(FnExpr { function, .. }) =
-                    args.first().and_then(|arg| arg.expr.as_fn_expr())
+        Expr::Fn(_) => {
+            return args.iter().find_map(|arg| match &*arg.expr {
+                Expr::Fn(FnExpr { function, .. })
+                    if arg.spread.is_none() && is_factory(function) =>
                 {
-                    let params = &*function.params;
-                    if !params.is_empty() {
-                        if let Pat::Ident(param) = &params[0].pat {
-                            if &*param.id.sym == "require" {
-                                return Some(param.to_id());
-                            }
-                        }
-                    }
+                    Some(&**function)
                 }
-            }
+                _ => None,
+            });
         }
 
         _ => {}
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::Display,
+    hash::{Hash, Hasher},
 };
 
 use once_cell::sync::Lazy;
@@ -14,7 +15,7 @@ use swc_core::{
 };
 use turbo_rcstr::RcStr;
 use turbo_tasks::{FxIndexMap, FxIndexSet, Vc};
-use turbopack_core::{issue::IssueSource, source::Source};
+use turbopack_core::{issue::IssueSource, reference_type::ImportWithType, source::Source};
 
 use super::{top_level_await::has_top_level_await, JsValue, ModuleValue};
 use crate::{
@@ -38,7 +39,7 @@ static ANNOTATION_TRANSITION: Lazy<JsWord> =
 static ANNOTATION_CHUNKING_TYPE: Lazy<JsWord> =
     Lazy::new(|| crate::annotations::ANNOTATION_CHUNKING_TYPE.into());
 
-/// Changes the type of the resolved module (only "json" is supported currently)
+/// Changes the type of the resolved module ("json", "text" and "bytes" are supported currently)
 static ATTRIBUTE_MODULE_TYPE: Lazy<JsWord> = Lazy::new(|| "type".into());
 
 impl ImportAnnotations {
@@ -89,6 +90,12 @@ impl ImportAnnotations {
         self.get(&ATTRIBUTE_MODULE_TYPE)
     }
 
+    /// Returns the [ImportWithType] requested by the type attribute, if it is a
+    /// supported one
+    pub fn import_with_type(&self) -> Option<ImportWithType> {
+        ImportWithType::from_attribute(self.module_type()?)
+    }
+
     pub fn get(&self, key: &JsWord) -> Option<&str> {
         self.map.get(key).map(|w| w.as_str())
     }
@@ -204,7 +211,7 @@ pub(crate) enum ImportedSymbol {
     PartEvaluation(u32),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub(crate) struct ImportMapReference {
     pub module_path: JsWord,
     pub imported_symbol: ImportedSymbol,
@@ -212,6 +219,38 @@ pub(crate) struct ImportMapReference {
     pub issue_source: Option<Vc<IssueSource>>,
 }
 
+// The issue source is not part of the identity of a reference, so repeated
+// imports of the same module share a single reference, which points at the
+// first import.
+impl PartialEq for ImportMapReference {
+    fn eq(&self, other: &Self) -> bool {
+        self.module_path == other.module_path
+            && self.imported_symbol == other.imported_symbol
+            && self.annotations == other.annotations
+    }
+}
+
+impl Eq for ImportMapReference {}
+
+impl Hash for ImportMapReference {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.module_path.hash(state);
+        self.imported_symbol.hash(state);
+        self.annotations.hash(state);
+    }
+}
+
+impl ImportMapReference {
+    /// Returns true if the module path can't be a valid module specifier.
+    ///
+    /// The source of an import or re-export is always a string literal in valid
+    /// JavaScript, but non-standard AST producers may leave an empty string or
+    /// an uninterpolated template like `./${x}` there.
+    pub fn has_invalid_module_path(&self) -> bool {
+        self.module_path.trim().is_empty() || self.module_path.contains("${")
+    }
+}
+
 impl ImportMap {
     pub fn is_esm(&self, specified_type: SpecifiedModuleType) -> bool {
         if self.has_exports {
@@ -636,3 +675,175 @@ fn get_import_symbol_from_export(specifier: &ExportSpecifier) -> ImportedSymbol
         ExportSpecifier::Namespace(..) => ImportedSymbol::Exports,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::{FileName, SourceMap, SyntaxContext, GLOBALS},
+        ecma::{ast::EsVersion, parser::parse_file_as_program},
+    };
+
+    use super::*;
+
+    #[test]
+    fn invalid_reexport_sources() {
+        GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(
+                FileName::Anon.into(),
+                "export { a } from './a'; export * from './b'; export * from './c';".into(),
+            );
+            let mut program = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+
+            // The parser only produces string literals, so simulate an AST
+            // producer which doesn't.
+            let Program::Module(module) = &mut program else {
+                panic!("expected a module");
+            };
+            for (item, src) in module.body.iter_mut().zip(["./${x}", "", "./c"]) {
+                match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                        src: Some(s),
+                        ..
+                    }))
+                    | ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll { src: s, .. })) => {
+                        s.value = src.into();
+                    }
+                    _ => panic!("unexpected module item"),
+                }
+            }
+
+            let imports = ImportMap::analyze(&program, None, None);
+            let invalid = imports
+                .references()
+                .map(|r| (r.module_path.as_str(), r.has_invalid_module_path()))
+                .collect::<BTreeMap<_, _>>();
+            assert_eq!(
+                invalid,
+                BTreeMap::from([("./${x}", true), ("", true), ("./c", false)])
+            );
+            assert_eq!(imports.reexports().count(), 3);
+        })
+    }
+
+    #[test]
+    fn namespace_reexport() {
+        GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(FileName::Anon.into(), "export * as ns from './a';".into());
+            let program = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+
+            let imports = ImportMap::analyze(&program, None, None);
+            let reexports = imports.reexports().collect::<Vec<_>>();
+            let [(i, Reexport::Namespace { exported })] = &reexports[..] else {
+                panic!("expected a single namespace reexport, got {reexports:?}");
+            };
+            assert_eq!(&**exported, "ns");
+
+            let reference = imports.references().nth(*i).unwrap();
+            assert_eq!(&*reference.module_path, "./a");
+            assert_eq!(reference.imported_symbol, ImportedSymbol::Exports);
+        })
+    }
+    #[test]
+    fn repeated_imports() {
+        GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(
+                FileName::Anon.into(),
+                "import a from './a'; import b from './a'; import { c } from './a'; import d from \
+                 './a' with { type: 'json' };"
+                    .into(),
+            );
+            let program = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+
+            let imports = ImportMap::analyze(&program, None, None);
+            let references = imports
+                .references()
+                .map(|r| {
+                    (
+                        &*r.module_path,
+                        &r.imported_symbol,
+                        r.annotations.module_type().is_some(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                references,
+                [
+                    ("./a", &ImportedSymbol::ModuleEvaluation, false),
+                    ("./a", &ImportedSymbol::Symbol("default".into()), false),
+                    ("./a", &ImportedSymbol::Symbol("c".into()), false),
+                    ("./a", &ImportedSymbol::ModuleEvaluation, true),
+                    ("./a", &ImportedSymbol::Symbol("default".into()), true),
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn import_and_reexport_of_same_module() {
+        GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(
+                FileName::Anon.into(),
+                "import x from './m'; export { y } from './m';".into(),
+            );
+            let program = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+
+            let imports = ImportMap::analyze(&program, None, None);
+            assert!(imports.references().all(|r| &*r.module_path == "./m"));
+            // The module evaluation is shared by the import and the reexport.
+            assert_eq!(
+                imports
+                    .references()
+                    .map(|r| &r.imported_symbol)
+                    .collect::<Vec<_>>(),
+                [
+                    &ImportedSymbol::ModuleEvaluation,
+                    &ImportedSymbol::Symbol("default".into()),
+                    &ImportedSymbol::Symbol("y".into()),
+                ]
+            );
+
+            let (i, export) = imports
+                .get_binding(&("x".into(), SyntaxContext::empty()))
+                .unwrap();
+            assert_eq!((i, export.as_deref()), (1, Some("default")));
+
+            let reexports = imports.reexports().collect::<Vec<_>>();
+            let [(2, Reexport::Named { imported, exported })] = &reexports[..] else {
+                panic!("expected a single named reexport, got {reexports:?}");
+            };
+            assert_eq!((&**imported, &**exported), ("y", "y"));
+        })
+    }
+}
@@ -1649,6 +1649,10 @@ impl JsValue {
                         "os",
                         "The Node.js os module: https://nodejs.org/api/os.html",
                     ),
+                    WellKnownObjectKind::ModuleModule | WellKnownObjectKind::ModuleModuleDefault => (
+                        "module",
+                        "The Node.js module module: https://nodejs.org/api/module.html",
+                    ),
                     WellKnownObjectKind::NodeProcess => (
                         "process",
                         "The Node.js process module: https://nodejs.org/api/process.html",
@@ -1731,10 +1735,18 @@ impl JsValue {
                         "url.pathToFileURL".to_string(),
                         "The Node.js url.pathToFileURL method: https://nodejs.org/api/url.html#urlpathtofileurlpath",
                     ),
+                    WellKnownFunctionKind::CreateRequire => (
+                        "module.createRequire".to_string(),
+                        "The Node.js module.createRequire method: https://nodejs.org/api/module.html#modulecreaterequirefilename",
+                    ),
                     WellKnownFunctionKind::ChildProcessSpawnMethod(name) => (
                         format!("child_process.{name}"),
                         "A process spawning method from the Node.js child_process module: https://nodejs.org/api/child_process.html",
                     ),
+                    WellKnownFunctionKind::ChildProcessExecMethod(name) => (
+                        format!("child_process.{name}"),
+                        "A shell command executing method from the Node.js child_process module: https://nodejs.org/api/child_process.html",
+                    ),
                     WellKnownFunctionKind::ChildProcessFork => (
                         "child_process.fork".to_string(),
                         "The Node.js child_process.fork method: https://nodejs.org/api/child_process.html#child_processforkmodulepath-args-options",
@@ -3628,6 +3640,8 @@ pub enum WellKnownObjectKind {
     ChildProcessDefault,
     OsModule,
     OsModuleDefault,
+    ModuleModule,
+    ModuleModuleDefault,
     NodeProcess,
     NodeProcessArgv,
     NodeProcessEnv,
@@ -3795,7 +3809,11 @@ pub enum WellKnownFunctionKind {
     Define,
     FsReadMethod(JsWord),
     PathToFileUrl,
+    /// `createRequire` from the Node.js module module, which returns a
+    /// `require` function.
+    CreateRequire,
     ChildProcessSpawnMethod(JsWord),
+    ChildProcessExecMethod(JsWord),
     ChildProcessFork,
     OsArch,
     OsPlatform,
@@ -3979,7 +3997,7 @@ mod tests {
     use std::{mem::take, path::PathBuf, time::Instant};
 
     use swc_core::{
-        common::{comments::SingleThreadedComments, Mark},
+        common::{comments::SingleThreadedComments, FileName, Mark, SourceMap, GLOBALS},
         ecma::{
             ast::EsVersion, parser::parse_file_as_program, transforms::base::resolver,
             visit::VisitMutWith,
@@ -3996,10 +4014,162 @@ mod tests {
     use super::{
         graph::{create_graph, ConditionalKind, Effect, EffectArg, EvalContext, VarGraph},
         linker::link,
-        JsValue,
+        JsValue, ObjectPart,
     };
     use crate::analyzer::imports::ImportAttributes;
 
+    #[test]
+    fn path_join() {
+        fn join(args: &[&str]) -> Option<String> {
+            let mut value =
+                super::well_known::path_join(args.iter().map(|&arg| arg.into()).collect());
+            value.normalize();
+            value.as_str().map(str::to_string)
+        }
+
+        assert_eq!(join(&["foo", "bar"]).as_deref(), Some("foo/bar"));
+        assert_eq!(join(&["foo/", "./bar/"]).as_deref(), Some("foo/bar/"));
+        assert_eq!(join(&["foo/", "bar"]).as_deref(), Some("foo/bar"));
+        assert_eq!(join(&["foo", "../"]).as_deref(), Some("./"));
+        assert_eq!(join(&["/foo", "../"]).as_deref(), Some("/"));
+        assert_eq!(join(&["/foo", "../../bar"]).as_deref(), Some("/bar"));
+        assert_eq!(join(&["foo", "..", ".."]).as_deref(), Some(".."));
+        assert_eq!(join(&[".", "foo"]).as_deref(), Some("foo"));
+        assert_eq!(join(&["foo", ".."]).as_deref(), Some("."));
+        assert_eq!(join(&["", "foo"]).as_deref(), Some("foo"));
+        assert_eq!(join(&["/", ".."]).as_deref(), Some("/"));
+        assert_eq!(join(&[]).as_deref(), Some("."));
+
+        let mut mixed = super::well_known::path_join(vec![
+            "foo".into(),
+            JsValue::unknown_empty(false, "dynamic"),
+            "bar".into(),
+        ]);
+        mixed.normalize();
+        assert_eq!(mixed.as_str(), None);
+    }
+
+    #[test]
+    fn effects_in_source_order() {
+        fn require_spans(effects: &[Effect], spans: &mut Vec<u32>) {
+            for effect in effects {
+                match effect {
+                    Effect::Call {
+                        func: JsValue::FreeVar(name),
+                        ..
+                    } if &**name == "require" => {
+                        spans.push(effect.span().unwrap().lo.0);
+                    }
+                    Effect::Conditional { kind, .. } => {
+                        if let ConditionalKind::If { then } = &**kind {
+                            require_spans(&then.effects, spans);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(
+                FileName::Anon.into(),
+                "require('./a'); import './b'; if (x) { require('./c'); } require('./d'); \
+                 foo(require('./e'), require('./f'));"
+                    .into(),
+            );
+            let mut m = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+            m.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+            let eval_context = EvalContext::new(&m, unresolved_mark, top_level_mark, None, None);
+
+            let var_graph = create_graph(&m, &eval_context);
+            let mut spans = Vec::new();
+            require_spans(&var_graph.effects, &mut spans);
+            assert_eq!(spans.len(), 5);
+            assert!(spans.is_sorted());
+        })
+    }
+
+    #[test]
+    fn object_spread_and_assign() {
+        fn object(parts: Vec<ObjectPart>) -> JsValue {
+            let mut object = JsValue::object(parts);
+            if let JsValue::Object { mutable, .. } = &mut object {
+                *mutable = false;
+            }
+            object
+        }
+        fn get(object: &JsValue, key: &str) -> JsValue {
+            let mut value = JsValue::member(Box::new(object.clone()), Box::new(key.into()));
+            while value.visit_mut(&mut super::builtin::replace_builtin) {}
+            value
+        }
+
+        // Object.assign({}, { ...{ ...{ json: "./json" } }, text: "./text" }, { text: "./txt" })
+        let base = object(vec![ObjectPart::KeyValue("json".into(), "./json".into())]);
+        let nested = object(vec![
+            ObjectPart::Spread(object(vec![ObjectPart::Spread(base)])),
+            ObjectPart::KeyValue("text".into(), "./text".into()),
+        ]);
+        let assigned = super::well_known::object_assign(vec![
+            object(vec![]),
+            nested,
+            object(vec![ObjectPart::KeyValue("text".into(), "./txt".into())]),
+        ]);
+
+        assert_eq!(get(&assigned, "json").as_str(), Some("./json"));
+        assert_eq!(get(&assigned, "text").as_str(), Some("./txt"));
+        assert_eq!(get(&assigned, "yaml"), JsValue::FreeVar("undefined".into()));
+    }
+
+    #[test]
+    fn fs_promises_members() {
+        use super::{
+            imports::ImportAnnotations, well_known::fs_module_member, ModuleValue,
+            WellKnownFunctionKind, WellKnownObjectKind,
+        };
+
+        let read_file =
+            JsValue::WellKnownFunction(WellKnownFunctionKind::FsReadMethod("readFile".into()));
+
+        // import { readFile } from "fs/promises"
+        let module = crate::utils::module_value_to_well_known_object(&ModuleValue {
+            module: "fs/promises".into(),
+            annotations: ImportAnnotations::default(),
+        });
+        assert_eq!(
+            module,
+            Some(JsValue::WellKnownObject(
+                WellKnownObjectKind::FsModulePromises
+            ))
+        );
+        assert_eq!(
+            fs_module_member(WellKnownObjectKind::FsModulePromises, "readFile".into()),
+            read_file
+        );
+
+        // import fs from "fs/promises"; fs.readFile
+        assert_eq!(
+            fs_module_member(WellKnownObjectKind::FsModulePromises, "default".into()),
+            JsValue::WellKnownObject(WellKnownObjectKind::FsModulePromises)
+        );
+
+        // require("fs").promises.readFile
+        assert_eq!(
+            fs_module_member(WellKnownObjectKind::FsModule, "promises".into()),
+            JsValue::WellKnownObject(WellKnownObjectKind::FsModulePromises)
+        );
+    }
+
     #[fixture("tests/analyzer/graph/**/input.js")]
     fn fixture(input: PathBuf) {
         crate::register();
@@ -63,6 +63,9 @@ pub async fn well_known_function_call(
             "import() is not supported",
         ),
         WellKnownFunctionKind::Require => require(args),
+        WellKnownFunctionKind::CreateRequire => {
+            JsValue::WellKnownFunction(WellKnownFunctionKind::Require)
+        }
         WellKnownFunctionKind::RequireContextRequire(value) => {
             require_context_require(value, args).await?
         }
@@ -166,15 +169,20 @@ pub fn object_assign(args: Vec<JsValue>) -> JsValue {
 }
 
 pub fn path_join(args: Vec<JsValue>) -> JsValue {
-    if args.is_empty() {
-        return ".".into();
-    }
     let mut parts = Vec::new();
+    // Like in Node.js, a trailing separator of the last segment is preserved
+    let mut trailing_separator = false;
     for item in args {
         if let Some(str) = item.as_str() {
+            // Like in Node.js, zero-length segments are ignored
+            if str.is_empty() {
+                continue;
+            }
+            trailing_separator = str.ends_with('/');
             let splitted = str.split('/');
             parts.extend(splitted.map(|s| s.into()));
         } else {
+            trailing_separator = false;
             parts.push(item);
         }
     }
@@ -183,13 +191,17 @@ pub fn path_join(args: Vec<JsValue>) -> JsValue {
     for item in parts {
         if let Some(str) = item.as_str() {
             match str {
-                "" | "." => {
+                "" => {
+                    // A leading separator makes the path absolute
                     if results_final.is_empty() && results.is_empty() {
                         results_final.push(item);
                     }
                 }
+                "." => {}
                 ".." => {
-                    if results.pop().is_none() {
+                    // `..` can't leave the root of an absolute path
+                    let is_root = matches!(&results_final[..], [root] if root.as_str() == Some(""));
+                    if results.pop().is_none() && !is_root {
                         results_final.push(item);
                     }
                 }
@@ -201,6 +213,12 @@ pub fn path_join(args: Vec<JsValue>) -> JsValue {
         }
     }
     results_final.append(&mut results);
+    match &results_final[..] {
+        [] if trailing_separator => return "./".into(),
+        [] => return ".".into(),
+        [root] if root.as_str() == Some("") => return "/".into(),
+        _ => {}
+    }
     let mut iter = results_final.into_iter();
     let first = iter.next().unwrap();
     let mut last_is_str = first.as_str().is_some();
@@ -215,6 +233,9 @@ pub fn path_join(args: Vec<JsValue>) -> JsValue {
         results.push(part);
         last_is_str = is_str;
     }
+    if trailing_separator {
+        results.push("/".into());
+    }
     JsValue::concat(results)
 }
 
@@ -578,6 +599,9 @@ pub async fn well_known_object_member(
         WellKnownObjectKind::OsModule | WellKnownObjectKind::OsModuleDefault => {
             os_module_member(kind, prop)
         }
+        WellKnownObjectKind::ModuleModule | WellKnownObjectKind::ModuleModuleDefault => {
+            module_module_member(kind, prop)
+        }
         WellKnownObjectKind::NodeProcess => node_process_member(prop, compile_time_info).await?,
         WellKnownObjectKind::NodePreGyp => node_pre_gyp(prop),
         WellKnownObjectKind::NodeExpressApp => express(prop),
@@ -633,7 +657,7 @@ pub fn path_module_member(kind: WellKnownObjectKind, prop: JsValue) -> JsValue {
 
 pub fn fs_module_member(kind: WellKnownObjectKind, prop: JsValue) -> JsValue {
     if let Some(word) = prop.as_str() {
-        match (kind, word) {
+        match (&kind, word) {
             (
                 ..,
                 "realpath" | "realpathSync" | "stat" | "statSync" | "existsSync"
@@ -649,14 +673,14 @@ pub fn fs_module_member(kind: WellKnownObjectKind, prop: JsValue) -> JsValue {
             (WellKnownObjectKind::FsModule, "default") => {
                 return JsValue::WellKnownObject(WellKnownObjectKind::FsModuleDefault)
             }
+            (WellKnownObjectKind::FsModulePromises, "default") => {
+                return JsValue::WellKnownObject(WellKnownObjectKind::FsModulePromises)
+            }
             _ => {}
         }
     }
     JsValue::unknown(
-        JsValue::member(
-            Box::new(JsValue::WellKnownObject(WellKnownObjectKind::FsModule)),
-            Box::new(prop),
-        ),
+        JsValue::member(Box::new(JsValue::WellKnownObject(kind)), Box::new(prop)),
         true,
         "unsupported property on Node.js fs module",
     )
@@ -689,6 +713,9 @@ pub fn child_process_module_member(kind: WellKnownObjectKind, prop: JsValue) ->
                 prop_str.unwrap().into(),
             ))
         }
+        (.., Some("exec" | "execSync")) => JsValue::WellKnownFunction(
+            WellKnownFunctionKind::ChildProcessExecMethod(prop_str.unwrap().into()),
+        ),
         (.., Some("fork")) => JsValue::WellKnownFunction(WellKnownFunctionKind::ChildProcessFork),
         (WellKnownObjectKind::ChildProcess, Some("default")) => {
             JsValue::WellKnownObject(WellKnownObjectKind::ChildProcessDefault)
@@ -724,6 +751,25 @@ fn os_module_member(kind: WellKnownObjectKind, prop: JsValue) -> JsValue {
     }
 }
 
+fn module_module_member(kind: WellKnownObjectKind, prop: JsValue) -> JsValue {
+    match (kind, prop.as_str()) {
+        (.., Some("createRequire")) => {
+            JsValue::WellKnownFunction(WellKnownFunctionKind::CreateRequire)
+        }
+        (WellKnownObjectKind::ModuleModule, Some("default")) => {
+            JsValue::WellKnownObject(WellKnownObjectKind::ModuleModuleDefault)
+        }
+        _ => JsValue::unknown(
+            JsValue::member(
+                Box::new(JsValue::WellKnownObject(WellKnownObjectKind::ModuleModule)),
+                Box::new(prop),
+            ),
+            true,
+            "unsupported property on Node.js module module",
+        ),
+    }
+}
+
 async fn node_process_member(
     prop: JsValue,
     compile_time_info: Vc<CompileTimeInfo>,
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use turbo_rcstr::RcStr;
 use turbo_tasks::{ResolvedVc, TryFlatJoinIterExt, Vc};
 use turbo_tasks_fs::{glob::Glob, FileJsonContent, FileSystemPath};
 use turbopack_core::{
@@ -193,6 +194,12 @@ pub enum EcmascriptExports {
     EsmExports(ResolvedVc<EsmExports>),
     DynamicNamespace,
     CommonJs,
+    /// A CommonJS module that only uses `module`, `exports` and top-level
+    /// `this` as the target of top-level `exports.foo = ...` or
+    /// `module.exports.foo = ...` statements. The names are exactly the
+    /// properties the module defines on `module.exports`, so `export *`
+    /// doesn't need a dynamic re-export for it.
+    CommonJsNamed(ResolvedVc<Vec<RcStr>>),
     EmptyCommonJs,
     Value,
     None,
@@ -7,6 +7,10 @@ pub mod failed_to_analyse {
         pub const CHILD_PROCESS_SPAWN: &str = "TP1005";
         pub const PATH_METHOD: &str = "TP1006";
         pub const REQUIRE_CONTEXT: &str = "TP1007";
+        pub const DYNAMIC_IMPORT_PASSTHROUGH: &str = "TP1008";
+        pub const UNSUPPORTED_IMPORT_TYPE: &str = "TP1009";
+        pub const INVALID_MODULE_SPECIFIER: &str = "TP1010";
+        pub const CHILD_PROCESS_EXEC: &str = "TP1011";
         pub const NODE_PRE_GYP_FIND: &str = "TP1100";
         pub const NODE_GYP_BUILD: &str = "TP1101";
         pub const NODE_BINDINGS: &str = "TP1102";
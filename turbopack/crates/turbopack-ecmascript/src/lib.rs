@@ -147,6 +147,16 @@ pub struct EcmascriptOptions {
     /// If false, they will reference the whole directory. If true, they won't
     /// reference anything and lead to an runtime error instead.
     pub ignore_dynamic_requests: bool,
+    /// Keep `import()` calls without any static known part as runtime
+    /// `import()`s instead of referencing the whole directory or replacing
+    /// them with an error. Takes precedence over `ignore_dynamic_requests`
+    /// for `import()`. These calls are reported as info issues and recorded
+    /// as passthrough [references::unresolved_reference::UnresolvedReference]s.
+    pub passthrough_dynamic_imports: bool,
+    /// Skip files which are not published with their package (excluded by
+    /// the `files` field or `.npmignore`) when enumerating a directory inside
+    /// of `node_modules` for `require.context()`.
+    pub respect_package_files: bool,
 }
 
 #[turbo_tasks::value(serialization = "auto_for_input")]
@@ -11,7 +11,7 @@ use turbopack_core::{
     environment::ChunkLoading,
     issue::IssueSource,
     reference::ModuleReference,
-    reference_type::EcmaScriptModulesReferenceSubType,
+    reference_type::{EcmaScriptModulesReferenceSubType, ImportWithType},
     resolve::{origin::ResolveOrigin, parse::Request, ModuleResolveResult},
 };
 use turbopack_resolve::ecmascript::esm_resolve;
@@ -32,6 +32,9 @@ pub struct EsmAsyncAssetReference {
     pub issue_source: ResolvedVc<IssueSource>,
     pub in_try: bool,
     pub import_externals: bool,
+    /// The type from the `with` or `assert` import attribute of the options
+    /// argument, e.g. `import("./data.json", { with: { type: "json" } })`.
+    pub import_with_type: Option<ImportWithType>,
 }
 
 #[turbo_tasks::value_impl]
@@ -44,6 +47,7 @@ impl EsmAsyncAssetReference {
         issue_source: ResolvedVc<IssueSource>,
         in_try: bool,
         import_externals: bool,
+        import_with_type: Value<Option<ImportWithType>>,
     ) -> Vc<Self> {
         Self::cell(EsmAsyncAssetReference {
             origin,
@@ -52,10 +56,20 @@ impl EsmAsyncAssetReference {
             issue_source,
             in_try,
             import_externals,
+            import_with_type: import_with_type.into_value(),
         })
     }
 }
 
+impl EsmAsyncAssetReference {
+    fn reference_sub_type(&self) -> EcmaScriptModulesReferenceSubType {
+        match &self.import_with_type {
+            Some(ty) => EcmaScriptModulesReferenceSubType::ImportWithType(ty.clone()),
+            None => EcmaScriptModulesReferenceSubType::DynamicImport,
+        }
+    }
+}
+
 #[turbo_tasks::value_impl]
 impl ModuleReference for EsmAsyncAssetReference {
     #[turbo_tasks::function]
@@ -63,7 +77,7 @@ impl ModuleReference for EsmAsyncAssetReference {
         esm_resolve(
             *self.origin,
             *self.request,
-            Value::new(EcmaScriptModulesReferenceSubType::DynamicImport),
+            Value::new(self.reference_sub_type()),
             self.in_try,
             Some(*self.issue_source),
         )
@@ -102,7 +116,7 @@ impl CodeGenerateable for EsmAsyncAssetReference {
             esm_resolve(
                 *self.origin,
                 *self.request,
-                Value::new(EcmaScriptModulesReferenceSubType::DynamicImport),
+                Value::new(self.reference_sub_type()),
                 self.in_try,
                 Some(*self.issue_source),
             ),
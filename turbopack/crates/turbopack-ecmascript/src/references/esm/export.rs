@@ -59,7 +59,9 @@ pub async fn is_export_missing(
     let exports = match &*exports {
         EcmascriptExports::None => return Ok(Vc::cell(true)),
         EcmascriptExports::Value => return Ok(Vc::cell(false)),
-        EcmascriptExports::CommonJs => return Ok(Vc::cell(false)),
+        EcmascriptExports::CommonJs | EcmascriptExports::CommonJsNamed(_) => {
+            return Ok(Vc::cell(false))
+        }
         EcmascriptExports::EmptyCommonJs => return Ok(Vc::cell(export_name != "default")),
         EcmascriptExports::DynamicNamespace => return Ok(Vc::cell(false)),
         EcmascriptExports::EsmExports(exports) => *exports,
@@ -87,6 +89,7 @@ pub async fn is_export_missing(
         match &*exports {
             EcmascriptExports::Value
             | EcmascriptExports::CommonJs
+            | EcmascriptExports::CommonJsNamed(_)
             | EcmascriptExports::DynamicNamespace => {
                 return Ok(Vc::cell(false));
             }
@@ -264,6 +267,12 @@ async fn handle_declared_export(
     }))
 }
 
+/// `export *` skips the `default` export and the `__esModule` interop marker
+/// of CommonJS modules.
+fn is_star_reexported_cjs_export(name: &str) -> bool {
+    name != "default" && name != "__esModule"
+}
+
 #[turbo_tasks::value]
 struct AllExportNamesResult {
     esm_exports: FxIndexMap<RcStr, ResolvedVc<Box<dyn EcmascriptChunkPlaceable>>>,
@@ -275,12 +284,27 @@ async fn get_all_export_names(
     module: ResolvedVc<Box<dyn EcmascriptChunkPlaceable>>,
 ) -> Result<Vc<AllExportNamesResult>> {
     let exports = module.get_exports().await?;
-    let EcmascriptExports::EsmExports(exports) = &*exports else {
-        return Ok(AllExportNamesResult {
-            esm_exports: FxIndexMap::default(),
-            dynamic_exporting_modules: vec![module],
+    let exports = match &*exports {
+        EcmascriptExports::EsmExports(exports) => exports,
+        EcmascriptExports::CommonJsNamed(names) => {
+            return Ok(AllExportNamesResult {
+                esm_exports: names
+                    .await?
+                    .iter()
+                    .filter(|n| is_star_reexported_cjs_export(n))
+                    .map(|n| (n.clone(), module))
+                    .collect(),
+                dynamic_exporting_modules: Vec::new(),
+            }
+            .cell());
+        }
+        _ => {
+            return Ok(AllExportNamesResult {
+                esm_exports: FxIndexMap::default(),
+                dynamic_exporting_modules: vec![module],
+            }
+            .cell());
         }
-        .cell());
     };
 
     let exports = exports.await?;
@@ -394,6 +418,15 @@ pub async fn expand_star_exports(
                 )
                 .await?;
             }
+            EcmascriptExports::CommonJsNamed(names) => {
+                set.extend(
+                    names
+                        .await?
+                        .iter()
+                        .filter(|n| is_star_reexported_cjs_export(n))
+                        .cloned(),
+                );
+            }
             EcmascriptExports::DynamicNamespace => {
                 has_dynamic_exports = true;
             }
@@ -14,12 +14,13 @@ pub mod require_context;
 pub mod type_issue;
 pub mod typescript;
 pub mod unreachable;
+pub mod unresolved_reference;
 pub mod util;
 pub mod worker;
 
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     mem::take,
     sync::Arc,
 };
@@ -63,7 +64,7 @@ use turbopack_core::{
     issue::{analyze::AnalyzeIssue, IssueExt, IssueSeverity, IssueSource, StyledString},
     module::Module,
     reference::{ModuleReference, ModuleReferences, SourceMapReference},
-    reference_type::{CommonJsReferenceSubType, ReferenceType},
+    reference_type::{CommonJsReferenceSubType, ImportWithType, ReferenceType},
     resolve::{
         find_context_file,
         origin::{PlainResolveOrigin, ResolveOrigin, ResolveOriginExt},
@@ -80,6 +81,7 @@ use turbopack_resolve::{
 };
 use turbopack_swc_utils::emitter::IssueEmitter;
 use unreachable::Unreachable;
+use unresolved_reference::{UnresolvedReference, UnresolvedReferenceKind, UnresolvedReferences};
 use worker::WorkerAssetReference;
 
 use self::{
@@ -110,7 +112,7 @@ use super::{
     special_cases::special_cases,
     utils::js_value_to_pattern,
     webpack::{
-        parse::{webpack_runtime, WebpackRuntime},
+        parse::{first_webpack_runtime, WebpackRuntime},
         WebpackChunkAssetReference, WebpackEntryAssetReference, WebpackRuntimeAssetReference,
     },
     EcmascriptModuleAssetType, ModuleTypeResult,
@@ -158,6 +160,8 @@ pub struct AnalyzeEcmascriptModuleResult {
     /// `true` when the analysis was successful.
     pub successful: bool,
     pub source_map: ResolvedVc<OptionSourceMap>,
+    /// Calls whose request is too dynamic to be statically analysed.
+    pub unresolved_references: ResolvedVc<UnresolvedReferences>,
 }
 
 /// A temporary analysis result builder to pass around, to be turned into an
@@ -173,6 +177,7 @@ pub struct AnalyzeEcmascriptModuleResultBuilder {
     successful: bool,
     source_map: Option<ResolvedVc<OptionSourceMap>>,
     bindings: Vec<EsmBinding>,
+    unresolved_references: Vec<UnresolvedReference>,
 }
 
 impl AnalyzeEcmascriptModuleResultBuilder {
@@ -188,6 +193,7 @@ impl AnalyzeEcmascriptModuleResultBuilder {
             successful: false,
             source_map: None,
             bindings: Vec::new(),
+            unresolved_references: Vec::new(),
         }
     }
 
@@ -254,6 +260,12 @@ impl AnalyzeEcmascriptModuleResultBuilder {
         self.bindings.push(binding);
     }
 
+    /// Adds a call whose request couldn't be statically analysed to the
+    /// analysis result.
+    pub fn add_unresolved_reference(&mut self, unresolved_reference: UnresolvedReference) {
+        self.unresolved_references.push(unresolved_reference);
+    }
+
     /// Sets the analysis result ES export.
     pub fn set_source_map(&mut self, source_map: ResolvedVc<OptionSourceMap>) {
         self.source_map = Some(source_map);
@@ -326,6 +338,7 @@ impl AnalyzeEcmascriptModuleResultBuilder {
                 async_module: self.async_module,
                 successful: self.successful,
                 source_map,
+                unresolved_references: ResolvedVc::cell(self.unresolved_references),
             },
         ))
     }
@@ -352,6 +365,8 @@ struct AnalysisState<'a> {
     tree_shaking_mode: Option<TreeShakingMode>,
     import_externals: bool,
     ignore_dynamic_requests: bool,
+    passthrough_dynamic_imports: bool,
+    respect_package_files: bool,
     url_rewrite_behavior: Option<UrlRewriteBehavior>,
 }
 
@@ -597,8 +612,66 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         set_handler_and_globals(&handler, globals, || create_graph(program, eval_context));
 
     let mut evaluation_references = Vec::new();
+    // Without tree shaking every import of a request refers to the whole module,
+    // so imports and reexports of the same request share a single reference.
+    let mut whole_module_references = HashMap::new();
 
     for (i, r) in eval_context.imports.references().enumerate() {
+        if r.has_invalid_module_path() {
+            AnalyzeIssue::new(
+                IssueSeverity::Warning.cell(),
+                source.ident(),
+                Vc::cell("invalid module specifier".into()),
+                StyledString::Text(
+                    format!(
+                        "\"{}\" is not a valid module specifier. Import and export sources must \
+                         be plain string literals.",
+                        r.module_path
+                    )
+                    .into(),
+                )
+                .cell(),
+                Some(errors::failed_to_analyse::ecmascript::INVALID_MODULE_SPECIFIER.into()),
+                r.issue_source,
+            )
+            .to_resolved()
+            .await?
+            .emit();
+        }
+
+        if let Some(ty) = r.annotations.module_type() {
+            if r.annotations.import_with_type().is_none() {
+                AnalyzeIssue::new(
+                    IssueSeverity::Warning.cell(),
+                    source.ident(),
+                    Vc::cell("unsupported import type".into()),
+                    StyledString::Text(
+                        format!(
+                            "The import type \"{ty}\" is not supported and will be ignored. \
+                             Supported types are \"json\", \"text\" and \"bytes\"."
+                        )
+                        .into(),
+                    )
+                    .cell(),
+                    Some(errors::failed_to_analyse::ecmascript::UNSUPPORTED_IMPORT_TYPE.into()),
+                    r.issue_source,
+                )
+                .to_resolved()
+                .await?
+                .emit();
+            }
+        }
+
+        if options.tree_shaking_mode.is_none() {
+            if let Some(&reference) = whole_module_references.get(&(&r.module_path, &r.annotations))
+            {
+                evaluation_references.push(i);
+                import_references.push(reference);
+                continue;
+            }
+        }
+
+        let key = (&r.module_path, &r.annotations);
         let r = EsmAssetReference::new(
             *origin,
             Request::parse(Value::new(RcStr::from(&*r.module_path).into())),
@@ -640,6 +713,9 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         .to_resolved()
         .await?;
 
+        if options.tree_shaking_mode.is_none() {
+            whole_module_references.insert(key, r);
+        }
         import_references.push(r);
     }
 
@@ -790,7 +866,7 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         EcmascriptExports::EsmExports(esm_exports.to_resolved().await?)
     } else if specified_type == SpecifiedModuleType::EcmaScript {
         match detect_dynamic_export(program) {
-            DetectedDynamicExportType::CommonJs => {
+            DetectedDynamicExportType::CommonJs(_) => {
                 SpecifiedModuleTypeIssue {
                     path: source.ident().path().to_resolved().await?,
                     specified_type,
@@ -819,7 +895,10 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         }
     } else {
         match detect_dynamic_export(program) {
-            DetectedDynamicExportType::CommonJs => EcmascriptExports::CommonJs,
+            DetectedDynamicExportType::CommonJs(Some(names)) => {
+                EcmascriptExports::CommonJsNamed(ResolvedVc::cell(names))
+            }
+            DetectedDynamicExportType::CommonJs(None) => EcmascriptExports::CommonJs,
             DetectedDynamicExportType::Namespace => EcmascriptExports::DynamicNamespace,
             DetectedDynamicExportType::Value => EcmascriptExports::Value,
             DetectedDynamicExportType::UsingModuleDeclarations => EcmascriptExports::EsmExports(
@@ -873,6 +952,8 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         tree_shaking_mode: options.tree_shaking_mode,
         import_externals: options.import_externals,
         ignore_dynamic_requests: options.ignore_dynamic_requests,
+        passthrough_dynamic_imports: options.passthrough_dynamic_imports,
+        respect_package_files: options.respect_package_files,
         url_rewrite_behavior: options.url_rewrite_behavior,
     };
 
@@ -1332,6 +1413,8 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
         source,
         compile_time_info,
         ignore_dynamic_requests,
+        passthrough_dynamic_imports,
+        respect_package_files,
         url_rewrite_behavior,
         ..
     } = state;
@@ -1372,6 +1455,13 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                 {
                     if meta_prop.as_str() == "url" {
                         let pat = js_value_to_pattern(url);
+                        // Absolute http(s) URLs ignore the base and don't point to anything in
+                        // the module graph, so the call is left untouched.
+                        if let Pattern::Constant(url) = &pat {
+                            if url.starts_with("http://") || url.starts_with("https://") {
+                                return Ok(());
+                            }
+                        }
                         if !pat.has_constant_parts() {
                             let (args, hints) = explain_args(&args);
                             handler.span_warn_with_code(
@@ -1484,10 +1574,39 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
         }
         JsValue::WellKnownFunction(WellKnownFunctionKind::Import) => {
             let args = linked_args(args).await?;
-            if args.len() == 1 {
+            if args.len() == 1 || args.len() == 2 {
                 let pat = js_value_to_pattern(&args[0]);
                 if !pat.has_constant_parts() {
                     let (args, hints) = explain_args(&args);
+                    if passthrough_dynamic_imports {
+                        // The `import()` is kept as is for the runtime to resolve, which is
+                        // intended and only reported for information.
+                        AnalyzeIssue::new(
+                            IssueSeverity::Info.cell(),
+                            source.ident(),
+                            Vc::cell("dynamic import left to the runtime".into()),
+                            StyledString::Text(
+                                format!("import({args}) is left to be resolved at runtime{hints}")
+                                    .into(),
+                            )
+                            .cell(),
+                            Some(
+                                errors::failed_to_analyse::ecmascript::DYNAMIC_IMPORT_PASSTHROUGH
+                                    .into(),
+                            ),
+                            Some(issue_source(*source, span)),
+                        )
+                        .to_resolved()
+                        .await?
+                        .emit();
+                        analysis.add_unresolved_reference(UnresolvedReference {
+                            source: issue_source(*source, span).to_resolved().await?,
+                            kind: UnresolvedReferenceKind::DynamicImport,
+                            args_repr: args.into(),
+                            passthrough: true,
+                        });
+                        return Ok(());
+                    }
                     handler.span_warn_with_code(
                         span,
                         &format!("import({args}) is very dynamic{hints}",),
@@ -1495,6 +1614,12 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                             errors::failed_to_analyse::ecmascript::DYNAMIC_IMPORT.to_string(),
                         ),
                     );
+                    analysis.add_unresolved_reference(UnresolvedReference {
+                        source: issue_source(*source, span).to_resolved().await?,
+                        kind: UnresolvedReferenceKind::DynamicImport,
+                        args_repr: args.into(),
+                        passthrough: false,
+                    });
                     if ignore_dynamic_requests {
                         analysis.add_code_gen(DynamicExpression::new_promise(Vc::cell(
                             ast_path.to_vec(),
@@ -1510,6 +1635,7 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                         issue_source(*source, span),
                         in_try,
                         state.import_externals,
+                        Value::new(args.get(1).and_then(import_options_with_type)),
                     )
                     .to_resolved()
                     .await?,
@@ -1538,6 +1664,12 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                             errors::failed_to_analyse::ecmascript::REQUIRE.to_string(),
                         ),
                     );
+                    analysis.add_unresolved_reference(UnresolvedReference {
+                        source: issue_source(*source, span).to_resolved().await?,
+                        kind: UnresolvedReferenceKind::Require,
+                        args_repr: args.into(),
+                        passthrough: false,
+                    });
                     if ignore_dynamic_requests {
                         analysis.add_code_gen(DynamicExpression::new(Vc::cell(ast_path.to_vec())));
                         return Ok(());
@@ -1592,6 +1724,12 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                             errors::failed_to_analyse::ecmascript::REQUIRE_RESOLVE.to_string(),
                         ),
                     );
+                    analysis.add_unresolved_reference(UnresolvedReference {
+                        source: issue_source(*source, span).to_resolved().await?,
+                        kind: UnresolvedReferenceKind::RequireResolve,
+                        args_repr: args.into(),
+                        passthrough: false,
+                    });
                     if ignore_dynamic_requests {
                         analysis.add_code_gen(DynamicExpression::new(Vc::cell(ast_path.to_vec())));
                         return Ok(());
@@ -1650,6 +1788,7 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                     Vc::cell(ast_path.to_vec()),
                     Some(issue_source(*source, span)),
                     in_try,
+                    respect_package_files,
                 )
                 .to_resolved()
                 .await?,
@@ -1669,6 +1808,12 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                             errors::failed_to_analyse::ecmascript::FS_METHOD.to_string(),
                         ),
                     );
+                    analysis.add_unresolved_reference(UnresolvedReference {
+                        source: issue_source(*source, span).to_resolved().await?,
+                        kind: UnresolvedReferenceKind::FsReadMethod(name.as_str().into()),
+                        args_repr: args.into(),
+                        passthrough: false,
+                    });
                     if ignore_dynamic_requests {
                         return Ok(());
                     }
@@ -1832,6 +1977,48 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
                 ),
             )
         }
+        JsValue::WellKnownFunction(WellKnownFunctionKind::ChildProcessExecMethod(name)) => {
+            let args = linked_args(args).await?;
+
+            // Only a constant command can be split into the executable and its arguments.
+            if let Some(command) = args.first().and_then(|arg| arg.as_str()) {
+                let mut parts = command.split_whitespace();
+                match (parts.next(), parts.next()) {
+                    (Some("node"), Some(script)) => {
+                        analysis.add_reference(
+                            CjsAssetReference::new(
+                                *origin,
+                                Request::parse(Value::new(Pattern::Constant(script.into()))),
+                                issue_source(*source, span),
+                                in_try,
+                            )
+                            .to_resolved()
+                            .await?,
+                        );
+                    }
+                    (Some(executable), _) => {
+                        analysis.add_reference(
+                            FileSourceReference::new(
+                                *source,
+                                Pattern::new(Pattern::Constant(executable.into())),
+                            )
+                            .to_resolved()
+                            .await?,
+                        );
+                    }
+                    (None, _) => {}
+                }
+                return Ok(());
+            }
+            let (args, hints) = explain_args(&args);
+            handler.span_warn_with_code(
+                span,
+                &format!("child_process.{name}({args}) is not statically analyse-able{hints}",),
+                DiagnosticId::Lint(
+                    errors::failed_to_analyse::ecmascript::CHILD_PROCESS_EXEC.to_string(),
+                ),
+            )
+        }
         JsValue::WellKnownFunction(WellKnownFunctionKind::ChildProcessFork) => {
             let args = linked_args(args).await?;
             if !args.is_empty() {
@@ -3163,31 +3350,68 @@ impl VisitAstPath for ModuleReferencesVisitor<'_> {
                     {
                         self.webpack_entry = true;
                     }
-                    [webpack_require, property]
-                        if webpack_require == "__webpack_require__" && property == "X" =>
-                    {
-                        if let [_, ExprOrSpread {
-                            spread: None,
-                            expr: chunk_ids,
-                        }, _] = &call.args[..]
-                        {
-                            if let Some(array) = chunk_ids.as_array() {
-                                for elem in array.elems.iter().flatten() {
-                                    if let ExprOrSpread { spread: None, expr } = elem {
-                                        if let Some(lit) = expr.as_lit() {
-                                            self.webpack_chunks.push(lit.clone());
-                                        }
-                                    }
-                                }
+                    _ => self.webpack_chunks.extend(webpack_chunk_ids(&var, call)),
+                }
+            }
+        }
+        call.visit_children_with_ast_path(self, ast_path);
+    }
+}
+
+/// Returns the type attribute of the options argument of a dynamic `import()`,
+/// i.e. `{ with: { type: "json" } }` or the deprecated `{ assert: { type:
+/// "json" } }`. Options that aren't constant are ignored.
+fn import_options_with_type(options: &JsValue) -> Option<ImportWithType> {
+    fn get<'a>(object: &'a JsValue, key: &str) -> Option<&'a JsValue> {
+        let JsValue::Object { parts, .. } = object else {
+            return None;
+        };
+        parts.iter().rev().find_map(|part| match part {
+            ObjectPart::KeyValue(k, v) if k.as_str() == Some(key) => Some(v),
+            _ => None,
+        })
+    }
+    let attributes = get(options, "with").or_else(|| get(options, "assert"))?;
+    ImportWithType::from_attribute(get(attributes, "type")?.as_str()?)
+}
+
+/// Returns the chunk ids a call to the webpack runtime `callee` loads, either
+/// through `__webpack_require__.X(_, [ids], _)` or through the lazy
+/// `__webpack_require__.e(id)` that webpack emits for `import()`.
+fn webpack_chunk_ids(callee: &[String], call: &CallExpr) -> Vec<Lit> {
+    let mut chunk_ids = Vec::new();
+    match callee {
+        [webpack_require, property]
+            if webpack_require == "__webpack_require__" && property == "X" =>
+        {
+            if let [_, ExprOrSpread {
+                spread: None,
+                expr: ids,
+            }, _] = &call.args[..]
+            {
+                if let Some(array) = ids.as_array() {
+                    for elem in array.elems.iter().flatten() {
+                        if let ExprOrSpread { spread: None, expr } = elem {
+                            if let Some(lit) = expr.as_lit() {
+                                chunk_ids.push(lit.clone());
                             }
                         }
                     }
-                    _ => {}
                 }
             }
         }
-        call.visit_children_with_ast_path(self, ast_path);
+        [webpack_require, property]
+            if webpack_require == "__webpack_require__" && property == "e" =>
+        {
+            if let [ExprOrSpread { spread: None, expr }] = &call.args[..] {
+                if let Some(lit) = expr.as_lit() {
+                    chunk_ids.push(lit.clone());
+                }
+            }
+        }
+        _ => {}
     }
+    chunk_ids
 }
 
 #[turbo_tasks::function]
@@ -3208,11 +3432,10 @@ async fn resolve_as_webpack_runtime(
         options,
     );
 
-    if let Some(source) = *resolved.first_source().await? {
-        Ok(webpack_runtime(*source, transforms))
-    } else {
-        Ok(WebpackRuntime::None.into())
-    }
+    Ok(first_webpack_runtime(
+        resolved.primary_sources(),
+        transforms,
+    ))
 }
 
 // TODO enable serialization
@@ -3233,13 +3456,121 @@ pub fn is_swc_helper_import(import: &ImportDecl) -> bool {
 
 #[derive(Debug)]
 enum DetectedDynamicExportType {
-    CommonJs,
+    /// Contains the export names when every use of `module`, `exports` and
+    /// top-level `this` is a top-level static export assignment (see
+    /// [static_cjs_export_assignment] and [static_cjs_define_property]).
+    CommonJs(Option<Vec<RcStr>>),
     Namespace,
     Value,
     None,
     UsingModuleDeclarations,
 }
 
+/// Matches a top-level `exports.foo = ...` or `module.exports.foo = ...`
+/// statement (including chains like `exports.a = exports.b = ...`) and returns
+/// the assigned export names and the assigned value.
+fn static_cjs_export_assignment(stmt: &Stmt) -> Option<(Vec<&JsWord>, &Expr)> {
+    fn static_prop_name(prop: &MemberProp) -> Option<&JsWord> {
+        match prop {
+            MemberProp::Ident(ident) => Some(&ident.sym),
+            MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+                Expr::Lit(Lit::Str(str)) => Some(&str.value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_exports_object(expr: &Expr) -> bool {
+        match expr {
+            Expr::Ident(ident) => &*ident.sym == "exports",
+            Expr::Member(MemberExpr { obj, prop, .. }) => {
+                matches!(&**obj, Expr::Ident(ident) if &*ident.sym == "module")
+                    && static_prop_name(prop).is_some_and(|name| &**name == "exports")
+            }
+            _ => false,
+        }
+    }
+
+    let Stmt::Expr(ExprStmt { expr, .. }) = stmt else {
+        return None;
+    };
+    let mut names = Vec::new();
+    let mut value = &**expr;
+    while let Expr::Assign(AssignExpr {
+        op: AssignOp::Assign,
+        left: AssignTarget::Simple(SimpleAssignTarget::Member(member)),
+        right,
+        ..
+    }) = value
+    {
+        if !is_exports_object(&member.obj) {
+            break;
+        }
+        let Some(name) = static_prop_name(&member.prop) else {
+            break;
+        };
+        names.push(name);
+        value = &**right;
+    }
+    if names.is_empty() {
+        None
+    } else {
+        Some((names, value))
+    }
+}
+
+/// Matches a top-level `Object.defineProperty(exports, "foo", ...)` or
+/// `Object.defineProperty(module.exports, "foo", ...)` statement, like the
+/// `__esModule` marker emitted by TypeScript and Babel, and returns the
+/// defined export name and the property descriptor.
+fn static_cjs_define_property(stmt: &Stmt) -> Option<(&JsWord, &Expr)> {
+    let Stmt::Expr(ExprStmt { expr, .. }) = stmt else {
+        return None;
+    };
+    let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        args,
+        ..
+    }) = &**expr
+    else {
+        return None;
+    };
+    let Expr::Member(MemberExpr { obj, prop, .. }) = &**callee else {
+        return None;
+    };
+    if !matches!(&**obj, Expr::Ident(ident) if &*ident.sym == "Object")
+        || !matches!(prop, MemberProp::Ident(ident) if &*ident.sym == "defineProperty")
+    {
+        return None;
+    }
+    let [ExprOrSpread {
+        spread: None,
+        expr: target,
+    }, ExprOrSpread {
+        spread: None,
+        expr: name,
+    }, ExprOrSpread {
+        spread: None,
+        expr: descriptor,
+    }] = &args[..]
+    else {
+        return None;
+    };
+    let is_exports_object = match &**target {
+        Expr::Ident(ident) => &*ident.sym == "exports",
+        Expr::Member(MemberExpr { obj, prop, .. }) => {
+            matches!(&**obj, Expr::Ident(ident) if &*ident.sym == "module")
+                && matches!(prop, MemberProp::Ident(ident) if &*ident.sym == "exports")
+        }
+        _ => false,
+    };
+    match &**name {
+        Expr::Lit(Lit::Str(str)) if is_exports_object => Some((&str.value, &**descriptor)),
+        _ => None,
+    }
+}
+
 fn detect_dynamic_export(p: &Program) -> DetectedDynamicExportType {
     use swc_core::ecma::visit::{visit_obj_and_computed, Visit, VisitWith};
 
@@ -3258,9 +3589,14 @@ fn detect_dynamic_export(p: &Program) -> DetectedDynamicExportType {
 
     struct Visitor {
         cjs: bool,
+        /// `module`, `exports` or top-level `this` were used in a way that
+        /// doesn't allow to statically determine the export names.
+        cjs_dynamic: bool,
         value: bool,
         namespace: bool,
         found: bool,
+        /// Number of enclosing functions or classes, which rebind `this`.
+        this_scopes: usize,
     }
 
     impl Visit for Visitor {
@@ -3273,6 +3609,7 @@ fn detect_dynamic_export(p: &Program) -> DetectedDynamicExportType {
             // optimizations, which is acceptable.
             if &*i.sym == "module" || &*i.sym == "exports" {
                 self.cjs = true;
+                self.cjs_dynamic = true;
                 self.found = true;
             }
             if &*i.sym == "__turbopack_export_value__" {
@@ -3284,6 +3621,38 @@ fn detect_dynamic_export(p: &Program) -> DetectedDynamicExportType {
                 self.found = true;
             }
         }
+
+        fn visit_this_expr(&mut self, _: &ThisExpr) {
+            // Top-level `this` is `module.exports` in CommonJS
+            if self.this_scopes == 0 {
+                self.cjs_dynamic = true;
+            }
+        }
+
+        fn visit_function(&mut self, n: &Function) {
+            self.this_scopes += 1;
+            n.visit_children_with(self);
+            self.this_scopes -= 1;
+        }
+
+        fn visit_class(&mut self, n: &Class) {
+            self.this_scopes += 1;
+            n.visit_children_with(self);
+            self.this_scopes -= 1;
+        }
+
+        fn visit_getter_prop(&mut self, n: &GetterProp) {
+            self.this_scopes += 1;
+            n.visit_children_with(self);
+            self.this_scopes -= 1;
+        }
+
+        fn visit_setter_prop(&mut self, n: &SetterProp) {
+            self.this_scopes += 1;
+            n.visit_children_with(self);
+            self.this_scopes -= 1;
+        }
+
         fn visit_expr(&mut self, n: &Expr) {
             if self.found {
                 return;
@@ -3301,13 +3670,54 @@ fn detect_dynamic_export(p: &Program) -> DetectedDynamicExportType {
 
     let mut v = Visitor {
         cjs: false,
+        cjs_dynamic: false,
         value: false,
         namespace: false,
         found: false,
+        this_scopes: 0,
+    };
+    let mut cjs_export_names = BTreeSet::new();
+    let mut visit_stmt = |v: &mut Visitor, stmt: &Stmt| {
+        if let Some((names, value)) = static_cjs_export_assignment(stmt) {
+            v.cjs = true;
+            cjs_export_names.extend(names.into_iter().map(|name| RcStr::from(name.as_str())));
+            value.visit_with(v);
+        } else if let Some((name, descriptor)) = static_cjs_define_property(stmt) {
+            v.cjs = true;
+            cjs_export_names.insert(RcStr::from(name.as_str()));
+            descriptor.visit_with(v);
+        } else {
+            stmt.visit_with(v);
+        }
     };
-    p.visit_with(&mut v);
+    match p {
+        Program::Module(m) => {
+            for item in &m.body {
+                match item {
+                    ModuleItem::Stmt(stmt) => visit_stmt(&mut v, stmt),
+                    ModuleItem::ModuleDecl(decl) => decl.visit_with(&mut v),
+                }
+                if v.found {
+                    break;
+                }
+            }
+        }
+        Program::Script(s) => {
+            for stmt in &s.body {
+                visit_stmt(&mut v, stmt);
+                if v.found {
+                    break;
+                }
+            }
+        }
+    }
+
     if v.cjs {
-        DetectedDynamicExportType::CommonJs
+        // When the traversal stopped early not all statements were checked.
+        let names_are_static = !v.cjs_dynamic && !v.value && !v.namespace;
+        DetectedDynamicExportType::CommonJs(
+            names_are_static.then(|| cjs_export_names.into_iter().collect()),
+        )
     } else if v.value {
         DetectedDynamicExportType::Value
     } else if v.namespace {
@@ -3371,3 +3781,137 @@ fn maybe_decode_data_url(url: RcStr) -> Vc<OptionSourceMap> {
         Vc::cell(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::{FileName, SourceMap, GLOBALS},
+        ecma::{
+            ast::EsVersion,
+            parser::parse_file_as_program,
+            visit::{Visit, VisitWith},
+        },
+    };
+
+    use super::*;
+
+    fn cjs_export_names(src: &str) -> Option<Vec<RcStr>> {
+        GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(FileName::Anon.into(), src.into());
+            let program = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+            match detect_dynamic_export(&program) {
+                DetectedDynamicExportType::CommonJs(names) => names,
+                other => panic!("expected a CommonJS module, got {other:?}"),
+            }
+        })
+    }
+
+    #[test]
+    fn cjs_export_names_static() {
+        assert_eq!(
+            cjs_export_names(
+                "exports.b = 2; exports.a = function () { return this; }; \
+                 module.exports['default'] = exports.c = 3; exports.__esModule = true;"
+            ),
+            Some(vec![
+                "__esModule".into(),
+                "a".into(),
+                "b".into(),
+                "c".into(),
+                "default".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn cjs_export_names_define_property() {
+        assert_eq!(
+            cjs_export_names(
+                "Object.defineProperty(exports, \"__esModule\", { value: true }); \
+                 Object.defineProperty(module.exports, \"a\", { enumerable: true, get: function \
+                 () { return 1; } }); exports.b = 2;"
+            ),
+            Some(vec!["__esModule".into(), "a".into(), "b".into()])
+        );
+        assert_eq!(
+            cjs_export_names("Object.defineProperty(exports, name, { value: true });"),
+            None
+        );
+        assert_eq!(
+            cjs_export_names("Object.defineProperty(exports, \"a\", { value: exports });"),
+            None
+        );
+    }
+
+    #[test]
+    fn cjs_export_names_dynamic() {
+        assert_eq!(cjs_export_names("module.exports = { a: 1 };"), None);
+        assert_eq!(cjs_export_names("exports[name] = 1;"), None);
+        assert_eq!(cjs_export_names("exports.a = 1; use(exports);"), None);
+        assert_eq!(cjs_export_names("exports.a = 1; this.b = 2;"), None);
+        assert_eq!(
+            cjs_export_names("exports.a = 1; (() => this.b = 2)();"),
+            None
+        );
+        assert_eq!(cjs_export_names("if (cond) { exports.a = 1; }"), None);
+        assert_eq!(
+            cjs_export_names("exports.a = 1; function f() { exports.b = 2; }"),
+            None
+        );
+    }
+
+    #[test]
+    fn webpack_chunk_loads() {
+        struct ChunkIds(Vec<Lit>);
+
+        impl Visit for ChunkIds {
+            fn visit_call_expr(&mut self, call: &CallExpr) {
+                if let Callee::Expr(expr) = &call.callee {
+                    if let StaticExpr::FreeVar(var) = StaticAnalyser::default().evaluate_expr(expr)
+                    {
+                        self.0.extend(webpack_chunk_ids(&var, call));
+                    }
+                }
+                call.visit_children_with(self);
+            }
+        }
+
+        let src = "__webpack_require__.X(0, [179, \"vendor\"], () => \
+                   __webpack_require__(__webpack_require__.s = 42)); \
+                   Promise.all([__webpack_require__.e(592), __webpack_require__.e(\"shared\")]) \
+                   .then(__webpack_require__.bind(__webpack_require__, 592)); \
+                   __webpack_require__.e(id); __webpack_require__.f.j(7, []);";
+        let chunk_ids = GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(FileName::Anon.into(), src.into());
+            let program = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+            let mut visitor = ChunkIds(Vec::new());
+            program.visit_with(&mut visitor);
+            visitor.0
+        });
+        let chunk_ids = chunk_ids
+            .iter()
+            .map(|lit| match lit {
+                Lit::Num(num) => num.value.to_string(),
+                Lit::Str(str) => str.value.to_string(),
+                other => panic!("unexpected chunk id {other:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(chunk_ids, ["179", "vendor", "592", "shared"]);
+    }
+}
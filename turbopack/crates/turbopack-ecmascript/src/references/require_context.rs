@@ -13,8 +13,8 @@ use swc_core::{
     quote, quote_expr,
 };
 use turbo_rcstr::RcStr;
-use turbo_tasks::{primitives::Regex, FxIndexMap, ResolvedVc, Value, ValueToString, Vc};
-use turbo_tasks_fs::{DirectoryContent, DirectoryEntry, FileSystemPath};
+use turbo_tasks::{primitives::Regex, FxIndexMap, ReadRef, ResolvedVc, Value, ValueToString, Vc};
+use turbo_tasks_fs::{glob::Glob, DirectoryContent, DirectoryEntry, FileContent, FileSystemPath};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::{
@@ -24,8 +24,12 @@ use turbopack_core::{
     ident::AssetIdent,
     issue::IssueSource,
     module::Module,
+    package_json::read_package_json,
     reference::{ModuleReference, ModuleReferences},
-    resolve::{origin::ResolveOrigin, parse::Request, ModuleResolveResult},
+    resolve::{
+        find_context_file, origin::ResolveOrigin, package_json, parse::Request,
+        FindContextFileResult, ModuleResolveResult,
+    },
     source::Source,
 };
 use turbopack_resolve::ecmascript::cjs_resolve;
@@ -168,15 +172,30 @@ impl RequireContextMap {
         filter: Vc<Regex>,
         issue_source: Option<ResolvedVc<IssueSource>>,
         is_optional: bool,
+        respect_package_files: bool,
     ) -> Result<Vc<Self>> {
         let origin_path = &*origin.origin_path().parent().await?;
 
         let list = &*FlatDirList::read(dir, recursive, filter).await?;
 
+        let publish_rules = if respect_package_files {
+            package_publish_rules(dir).await?
+        } else {
+            None
+        };
+
         let mut map = FxIndexMap::default();
 
         for (context_relative, path) in list {
-            if let Some(origin_relative) = origin_path.get_relative_path_to(&*path.await?) {
+            let path_value = &*path.await?;
+            if let Some((package_dir, rules)) = &publish_rules {
+                if let Some(package_relative) = package_dir.get_path_to(path_value) {
+                    if !rules.is_published(package_relative) {
+                        continue;
+                    }
+                }
+            }
+            if let Some(origin_relative) = origin_path.get_relative_path_to(path_value) {
                 let request = Request::parse(Value::new(origin_relative.clone().into()))
                     .to_resolved()
                     .await?;
@@ -201,6 +220,118 @@ impl RequireContextMap {
     }
 }
 
+/// The rules deciding which files of a package are published, taken from
+/// the `files` field of its `package.json` or from its `.npmignore`.
+enum PublishRules {
+    /// Only files matching one of the globs are published.
+    Files(Vec<Glob>),
+    /// Files matching a rule are not published, unless a later negated rule
+    /// matches them again.
+    Ignore(Vec<(bool, Glob)>),
+}
+
+impl PublishRules {
+    fn from_files_field<'a>(files: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut globs = Vec::new();
+        for entry in files {
+            let entry = entry.trim_start_matches("./").trim_matches('/');
+            if entry.is_empty() {
+                continue;
+            }
+            globs.push(Glob::parse(entry)?);
+            globs.push(Glob::parse(&format!("{entry}/**"))?);
+        }
+        Ok(PublishRules::Files(globs))
+    }
+
+    fn from_npmignore(content: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, pattern) = match line.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, line),
+            };
+            let directory_only = pattern.ends_with('/');
+            let pattern = pattern.trim_end_matches('/');
+            // Like `.gitignore`, a pattern without a slash matches at any depth.
+            let pattern = match pattern.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None if pattern.contains('/') => pattern.to_string(),
+                None => format!("**/{pattern}"),
+            };
+            if !directory_only {
+                rules.push((negated, Glob::parse(&pattern)?));
+            }
+            rules.push((negated, Glob::parse(&format!("{pattern}/**"))?));
+        }
+        Ok(PublishRules::Ignore(rules))
+    }
+
+    /// Returns whether the file at `path`, relative to the package root, is
+    /// published.
+    fn is_published(&self, path: &str) -> bool {
+        match self {
+            PublishRules::Files(globs) => {
+                is_always_published(path) || globs.iter().any(|glob| glob.execute(path))
+            }
+            PublishRules::Ignore(rules) => rules
+                .iter()
+                .rev()
+                .find(|(_, glob)| glob.execute(path))
+                .is_none_or(|(negated, _)| *negated),
+        }
+    }
+}
+
+/// npm always publishes the `package.json`, readme and license of a package.
+fn is_always_published(path: &str) -> bool {
+    let path = path.to_ascii_lowercase();
+    path == "package.json"
+        || (!path.contains('/')
+            && ["readme", "license", "licence"]
+                .iter()
+                .any(|name| path.starts_with(name)))
+}
+
+/// Finds the publish rules of the package in `node_modules` which contains
+/// `dir`, together with the package directory.
+async fn package_publish_rules(
+    dir: Vc<FileSystemPath>,
+) -> Result<Option<(ReadRef<FileSystemPath>, PublishRules)>> {
+    let FindContextFileResult::Found(package_json_path, _) =
+        &*find_context_file(dir, package_json()).await?
+    else {
+        return Ok(None);
+    };
+    let package_dir = package_json_path.parent().await?;
+    if !package_dir
+        .path
+        .split('/')
+        .any(|segment| segment == "node_modules")
+    {
+        return Ok(None);
+    }
+
+    if let Some(package_json) = &*read_package_json(**package_json_path).await? {
+        if let Some(files) = package_json.get("files").and_then(|files| files.as_array()) {
+            let rules = PublishRules::from_files_field(files.iter().filter_map(|f| f.as_str()))?;
+            return Ok(Some((package_dir, rules)));
+        }
+    }
+
+    let npmignore = package_json_path.parent().join(".npmignore".into());
+    if let FileContent::Content(content) = &*npmignore.read().await? {
+        let rules = PublishRules::from_npmignore(&content.content().to_str()?)?;
+        return Ok(Some((package_dir, rules)));
+    }
+
+    Ok(None)
+}
+
 /// A reference for `require.context()`, will replace it with an inlined map
 /// wrapped in `__turbopack_module_context__`;
 #[turbo_tasks::value]
@@ -227,6 +358,7 @@ impl RequireContextAssetReference {
         path: ResolvedVc<AstPath>,
         issue_source: Option<ResolvedVc<IssueSource>>,
         in_try: bool,
+        respect_package_files: bool,
     ) -> Result<Vc<Self>> {
         let map = RequireContextMap::generate(
             *origin,
@@ -235,6 +367,7 @@ impl RequireContextAssetReference {
             filter,
             issue_source.map(|v| *v),
             in_try,
+            respect_package_files,
         )
         .to_resolved()
         .await?;
@@ -535,3 +668,38 @@ impl ChunkItem for RequireContextChunkItem {
         *ResolvedVc::upcast(self.inner)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PublishRules;
+
+    #[test]
+    fn files_field() {
+        let rules = PublishRules::from_files_field(["lib/", "./index.js", "*.d.ts"]).unwrap();
+
+        assert!(rules.is_published("index.js"));
+        assert!(rules.is_published("lib/a.js"));
+        assert!(rules.is_published("lib/nested/b.js"));
+        assert!(rules.is_published("types.d.ts"));
+        assert!(rules.is_published("package.json"));
+        assert!(rules.is_published("README.md"));
+        assert!(!rules.is_published("test/a.js"));
+        assert!(!rules.is_published("libs/a.js"));
+    }
+
+    #[test]
+    fn npmignore() {
+        let rules = PublishRules::from_npmignore(
+            "# comment\n\ntest/\n*.spec.js\n/fixtures\n!keep.spec.js\n",
+        )
+        .unwrap();
+
+        assert!(rules.is_published("index.js"));
+        assert!(!rules.is_published("test/a.js"));
+        assert!(!rules.is_published("lib/test/a.js"));
+        assert!(!rules.is_published("lib/a.spec.js"));
+        assert!(rules.is_published("lib/keep.spec.js"));
+        assert!(!rules.is_published("fixtures/a.js"));
+        assert!(rules.is_published("lib/fixtures/a.js"));
+    }
+}
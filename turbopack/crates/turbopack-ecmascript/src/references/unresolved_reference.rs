@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{debug::ValueDebugFormat, trace::TraceRawVcs, ResolvedVc};
+use turbopack_core::issue::IssueSource;
+
+/// The call that created an [UnresolvedReference].
+#[derive(ValueDebugFormat, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub enum UnresolvedReferenceKind {
+    /// `import(...)`
+    DynamicImport,
+    /// `require(...)`
+    Require,
+    /// `require.resolve(...)`
+    RequireResolve,
+    /// A method of the `fs` module that reads a file, e.g. `fs.readFile(...)`.
+    FsReadMethod(RcStr),
+}
+
+/// A call whose request is too dynamic to be statically analysed, e.g.
+/// `import(variable)`.
+///
+/// These are reported as issues during the analysis, and collected into
+/// [UnresolvedReferences] for tools that need to enumerate them.
+#[derive(ValueDebugFormat, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct UnresolvedReference {
+    /// The location of the call.
+    pub source: ResolvedVc<IssueSource>,
+    pub kind: UnresolvedReferenceKind,
+    /// The explained arguments of the call, as printed in the warning.
+    pub args_repr: RcStr,
+    /// Whether the call is intentionally kept for the runtime to resolve, see
+    /// [crate::EcmascriptOptions::passthrough_dynamic_imports].
+    pub passthrough: bool,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct UnresolvedReferences(Vec<UnresolvedReference>);
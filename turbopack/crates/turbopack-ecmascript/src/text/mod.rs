@@ -15,6 +15,11 @@ fn modifier() -> Vc<RcStr> {
     Vc::cell("text content".into())
 }
 
+#[turbo_tasks::function]
+fn bytes_modifier() -> Vc<RcStr> {
+    Vc::cell("bytes content".into())
+}
+
 /// A source asset that exports the string content of an asset as the default
 /// export of a JS module.
 #[turbo_tasks::value]
@@ -55,3 +60,52 @@ impl Asset for TextContentFileSource {
         Ok(AssetContent::file(content))
     }
 }
+
+/// A source asset that exports the binary content of an asset as a
+/// `Uint8Array` default export of a JS module.
+#[turbo_tasks::value]
+pub struct BytesContentFileSource {
+    pub source: ResolvedVc<Box<dyn Source>>,
+}
+
+#[turbo_tasks::value_impl]
+impl BytesContentFileSource {
+    #[turbo_tasks::function]
+    pub fn new(source: ResolvedVc<Box<dyn Source>>) -> Vc<Self> {
+        BytesContentFileSource { source }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Source for BytesContentFileSource {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.source
+            .ident()
+            .with_modifier(bytes_modifier())
+            .rename_as("*.mjs".into())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for BytesContentFileSource {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<AssetContent>> {
+        let source = self.source.content().file_content();
+        let FileContent::Content(content) = &*source.await? else {
+            return Ok(AssetContent::file(FileContent::NotFound.cell()));
+        };
+        let bytes = content.content().to_bytes()?;
+        let mut code = String::with_capacity(bytes.len() * 4 + 40);
+        code.push_str("export default new Uint8Array([");
+        for (i, byte) in bytes.iter().enumerate() {
+            if i > 0 {
+                code.push(',');
+            }
+            code.push_str(&byte.to_string());
+        }
+        code.push_str("]);");
+        let content = FileContent::Content(RcStr::from(code).into()).cell();
+        Ok(AssetContent::file(content))
+    }
+}
@@ -135,13 +135,14 @@ impl Module for TsConfigModuleAsset {
                         .read_dir()
                         .await?
                     {
-                        all_types.extend(entries.iter().filter_map(|(name, _)| {
-                            if name.starts_with('.') {
-                                None
-                            } else {
-                                Some((self.source, name.clone()))
-                            }
-                        }));
+                        let mut names = entries
+                            .iter()
+                            .map(|(name, _)| name)
+                            .filter(|name| !name.starts_with('.'))
+                            .collect::<Vec<_>>();
+                        // The directory listing is unordered.
+                        names.sort();
+                        all_types.extend(names.into_iter().map(|name| (self.source, name.clone())));
                     }
                     let parent = current.parent().resolve().await?;
                     if parent == current {
@@ -44,10 +44,21 @@ pub fn js_value_to_pattern(value: &JsValue) -> Pattern {
         JsValue::Concat(_, parts) => {
             Pattern::Concatenation(parts.iter().map(js_value_to_pattern).collect())
         }
-        JsValue::Add(..) => {
-            // TODO do we need to handle that here
-            // or is that already covered by normalization of JsValue
-            Pattern::Dynamic
+        JsValue::Add(_, parts) => {
+            // Normalization turns this into a `Concat` when all operands are known to be
+            // strings. Otherwise `+` only concatenates from the first string operand onwards,
+            // the operands before it might be added numerically.
+            match parts.iter().position(|part| part.is_string() == Some(true)) {
+                Some(first_string) => {
+                    let mut patterns = Vec::with_capacity(parts.len() - first_string + 1);
+                    if first_string > 0 {
+                        patterns.push(Pattern::Dynamic);
+                    }
+                    patterns.extend(parts[first_string..].iter().map(js_value_to_pattern));
+                    Pattern::Concatenation(patterns)
+                }
+                None => Pattern::Dynamic,
+            }
         }
         _ => Pattern::Dynamic,
     };
@@ -162,13 +173,14 @@ pub fn module_value_to_well_known_object(module_value: &ModuleValue) -> Option<J
     Some(match &*module_value.module {
         "node:path" | "path" => JsValue::WellKnownObject(WellKnownObjectKind::PathModule),
         "node:fs/promises" | "fs/promises" => {
-            JsValue::WellKnownObject(WellKnownObjectKind::FsModule)
+            JsValue::WellKnownObject(WellKnownObjectKind::FsModulePromises)
         }
         "node:fs" | "fs" => JsValue::WellKnownObject(WellKnownObjectKind::FsModule),
         "node:child_process" | "child_process" => {
             JsValue::WellKnownObject(WellKnownObjectKind::ChildProcess)
         }
         "node:os" | "os" => JsValue::WellKnownObject(WellKnownObjectKind::OsModule),
+        "node:module" | "module" => JsValue::WellKnownObject(WellKnownObjectKind::ModuleModule),
         "node:process" | "process" => JsValue::WellKnownObject(WellKnownObjectKind::NodeProcess),
         "@mapbox/node-pre-gyp" => JsValue::WellKnownObject(WellKnownObjectKind::NodePreGyp),
         "node-gyp-build" => JsValue::WellKnownFunction(WellKnownFunctionKind::NodeGypBuild),
@@ -184,3 +196,46 @@ pub fn module_value_to_well_known_object(module_value: &ModuleValue) -> Option<J
         _ => return None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use turbopack_core::resolve::pattern::Pattern;
+
+    use super::js_value_to_pattern;
+    use crate::analyzer::JsValue;
+
+    fn unknown() -> JsValue {
+        JsValue::unknown_empty(false, "")
+    }
+
+    #[test]
+    fn add_to_pattern() {
+        assert_eq!(
+            js_value_to_pattern(&JsValue::add(vec!["./dir/".into(), unknown()])),
+            Pattern::Concatenation(vec![Pattern::Constant("./dir/".into()), Pattern::Dynamic])
+        );
+        assert_eq!(
+            js_value_to_pattern(&JsValue::add(vec![unknown(), "/index.js".into()])),
+            Pattern::Concatenation(vec![
+                Pattern::Dynamic,
+                Pattern::Constant("/index.js".into())
+            ])
+        );
+        assert_eq!(
+            js_value_to_pattern(&JsValue::add(vec!["./".into(), unknown(), ".js".into()])),
+            Pattern::Concatenation(vec![
+                Pattern::Constant("./".into()),
+                Pattern::Dynamic,
+                Pattern::Constant(".js".into())
+            ])
+        );
+        assert_eq!(
+            js_value_to_pattern(&JsValue::add(vec![unknown(), unknown(), ".js".into()])),
+            Pattern::Concatenation(vec![Pattern::Dynamic, Pattern::Constant(".js".into())])
+        );
+        assert_eq!(
+            js_value_to_pattern(&JsValue::add(vec![unknown(), unknown()])),
+            Pattern::Dynamic
+        );
+    }
+}
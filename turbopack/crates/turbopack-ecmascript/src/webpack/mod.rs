@@ -18,7 +18,10 @@ use turbopack_core::{
 };
 use turbopack_resolve::ecmascript::apply_cjs_specific_options;
 
-use self::{parse::WebpackRuntime, references::module_references};
+use self::{
+    parse::{chunk_request, WebpackRuntime},
+    references::module_references,
+};
 use crate::EcmascriptInputTransforms;
 
 pub mod parse;
@@ -88,17 +91,18 @@ impl ModuleReference for WebpackChunkAssetReference {
         let runtime = self.runtime.await?;
         Ok(match &*runtime {
             WebpackRuntime::Webpack5 {
-                chunk_request_expr: _,
+                chunk_request_expr,
                 context_path,
             } => {
-                // TODO determine filename from chunk_request_expr
                 let chunk_id = match &self.chunk_id {
                     Lit::Str(str) => str.value.to_string(),
-                    Lit::Num(num) => format!("{num}"),
+                    Lit::Num(num) => num.value.to_string(),
                     _ => todo!(),
                 };
-                let filename = format!("./chunks/{}.js", chunk_id).into();
-                let source = Vc::upcast(FileSource::new(context_path.join(filename)));
+                let Some(filename) = chunk_request(chunk_request_expr, &chunk_id) else {
+                    return Ok(ModuleResolveResult::unresolvable().cell());
+                };
+                let source = Vc::upcast(FileSource::new(context_path.join(filename.into())));
 
                 ModuleResolveResult::module(ResolvedVc::upcast(
                     WebpackModuleAsset::new(source, *self.runtime, *self.transforms)
@@ -5,19 +5,21 @@ use swc_core::{
     common::GLOBALS,
     ecma::{
         ast::{
-            ArrowExpr, AssignOp, AssignTarget, BinExpr, BinaryOp, CallExpr, Callee, Expr,
-            ExprOrSpread, ExprStmt, FnExpr, Lit, Module, ModuleItem, Program, Script,
-            SimpleAssignTarget, Stmt,
+            ArrowExpr, AssignOp, AssignTarget, BinExpr, BinaryOp, BlockStmtOrExpr, CallExpr,
+            Callee, Expr, ExprOrSpread, ExprStmt, FnExpr, Ident, Lit, Module, ModuleItem, Program,
+            Script, SimpleAssignTarget, Stmt,
         },
         visit::{Visit, VisitWith},
     },
 };
 use turbo_tasks::{ResolvedVc, Value, Vc};
 use turbo_tasks_fs::FileSystemPath;
-use turbopack_core::source::Source;
+use turbopack_core::source::{Source, Sources};
 
 use crate::{
-    analyzer::{graph::EvalContext, JsValue},
+    analyzer::{
+        graph::EvalContext, ConstantNumber, ConstantValue, JsValue, LogicalOperator, ObjectPart,
+    },
     parse::{parse, ParseResult},
     utils::unparen,
     EcmascriptInputTransforms, EcmascriptModuleAssetType,
@@ -147,17 +149,75 @@ fn get_fn_body(expr: &Expr) -> Option<&Vec<Stmt>> {
     None
 }
 
+/// Returns the first parameter and the returned expression of a function like
+/// `function (chunkId) { return ... }` or `(chunkId) => ...`.
+fn get_fn_param_and_return(expr: &Expr) -> Option<(&Ident, &Expr)> {
+    let expr = unparen(expr);
+    let (param, body) = if let Some(FnExpr { function, .. }) = expr.as_fn_expr() {
+        let param = function.params.first()?.pat.as_ident()?;
+        (param, function.body.as_ref()?.stmts.as_slice())
+    } else if let Some(ArrowExpr { params, body, .. }) = expr.as_arrow() {
+        let param = params.first()?.as_ident()?;
+        match &**body {
+            BlockStmtOrExpr::Expr(expr) => return Some((&param.id, &**expr)),
+            BlockStmtOrExpr::BlockStmt(block) => (param, block.stmts.as_slice()),
+        }
+    } else {
+        return None;
+    };
+    let ret = body.iter().find_map(|stmt| stmt.as_return_stmt())?;
+    Some((&param.id, ret.arg.as_deref()?))
+}
+
 fn get_javascript_chunk_filename(stmts: &Vec<Stmt>, eval_context: &EvalContext) -> Option<JsValue> {
-    if let Some(expr) = get_assignment(stmts, "__webpack_require__.u") {
-        if let Some(stmts) = get_fn_body(expr) {
-            if let Some(ret) = stmts.iter().find_map(|stmt| stmt.as_return_stmt()) {
-                if let Some(expr) = &ret.arg {
-                    return Some(eval_context.eval(expr));
+    let expr = get_assignment(stmts, "__webpack_require__.u")?;
+    let (param, ret) = get_fn_param_and_return(expr)?;
+    let param = param.to_id();
+    let mut value = eval_context.eval(ret);
+    value.visit_mut(&mut |value| {
+        if matches!(value, JsValue::Variable(id) if *id == param) {
+            *value = JsValue::FreeVar("chunkId".into());
+            true
+        } else {
+            false
+        }
+    });
+    Some(value)
+}
+
+/// Computes the request of a chunk from the `chunk_request_expr` of a
+/// [WebpackRuntime::Webpack5] by substituting the chunk id. Only the
+/// expressions webpack generates for `__webpack_require__.u` are supported:
+/// concatenations, lookups in object literals keyed by chunk id and `||`
+/// fallbacks.
+pub fn chunk_request(chunk_request_expr: &JsValue, chunk_id: &str) -> Option<String> {
+    match chunk_request_expr {
+        JsValue::FreeVar(name) if &**name == "chunkId" => Some(chunk_id.to_string()),
+        JsValue::Constant(ConstantValue::Num(ConstantNumber(num))) => Some(num.to_string()),
+        JsValue::Constant(value) => value.as_str().map(|str| str.to_string()),
+        JsValue::Add(_, parts) | JsValue::Concat(_, parts) => parts
+            .iter()
+            .map(|part| chunk_request(part, chunk_id))
+            .collect(),
+        JsValue::Logical(_, LogicalOperator::Or, parts) => parts
+            .iter()
+            .find_map(|part| chunk_request(part, chunk_id).filter(|str| !str.is_empty())),
+        JsValue::Member(_, obj, prop) => {
+            let JsValue::Object { parts, .. } = &**obj else {
+                return None;
+            };
+            let prop = chunk_request(prop, chunk_id)?;
+            parts.iter().rev().find_map(|part| match part {
+                ObjectPart::KeyValue(key, value)
+                    if chunk_request(key, chunk_id).as_deref() == Some(&*prop) =>
+                {
+                    chunk_request(value, chunk_id)
                 }
-            }
+                _ => None,
+            })
         }
+        _ => None,
     }
-    None
 }
 
 struct RequirePrefixVisitor {
@@ -243,3 +303,137 @@ pub async fn webpack_runtime(
     }
     Ok(WebpackRuntime::None.into())
 }
+
+/// Returns the runtime of the first of `sources` that is a webpack runtime,
+/// e. g. when a request resolves to multiple alternatives.
+#[turbo_tasks::function]
+pub async fn first_webpack_runtime(
+    sources: Vc<Sources>,
+    transforms: Vc<EcmascriptInputTransforms>,
+) -> Result<Vc<WebpackRuntime>> {
+    for &source in sources.await?.iter() {
+        let runtime = webpack_runtime(*source, transforms);
+        if let WebpackRuntime::Webpack5 { .. } = &*runtime.await? {
+            return Ok(runtime);
+        }
+    }
+    Ok(WebpackRuntime::None.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::{FileName, Mark, SourceMap, GLOBALS},
+        ecma::{
+            ast::EsVersion, parser::parse_file_as_program, transforms::base::resolver,
+            visit::VisitMutWith,
+        },
+    };
+    use turbo_tasks_fs::{File, FileSystem, VirtualFileSystem};
+    use turbopack_core::{asset::AssetContent, virtual_source::VirtualSource};
+
+    use super::*;
+
+    fn chunk_requests(src: &str, chunk_ids: &[&str]) -> Vec<Option<String>> {
+        GLOBALS.set(&Default::default(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(FileName::Anon.into(), src.into());
+            let mut program = parse_file_as_program(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+            program.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+            let eval_context =
+                EvalContext::new(&program, unresolved_mark, top_level_mark, None, None);
+            let Program::Script(script) = &program else {
+                panic!("expected a script");
+            };
+            let chunk_request_expr =
+                get_javascript_chunk_filename(&script.body, &eval_context).unwrap();
+            chunk_ids
+                .iter()
+                .map(|chunk_id| chunk_request(&chunk_request_expr, chunk_id))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn chunk_filename_template() {
+        assert_eq!(
+            chunk_requests(
+                "__webpack_require__.u = function (chunkId) { return \"\" + chunkId + \".js\"; };",
+                &["179", "vendor"]
+            ),
+            [Some("179.js".to_string()), Some("vendor.js".to_string())]
+        );
+    }
+
+    #[test]
+    fn chunk_filename_hash_map() {
+        assert_eq!(
+            chunk_requests(
+                "__webpack_require__.u = (e) => \"static/chunks/\" + ({ 592: \"about\" }[e] || e) \
+                 + \".\" + { 179: \"0a1b2c\", 592: \"3d4e5f\" }[e] + \".js\";",
+                &["179", "592", "404"]
+            ),
+            [
+                Some("static/chunks/179.0a1b2c.js".to_string()),
+                Some("static/chunks/about.3d4e5f.js".to_string()),
+                None
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn first_runtime_of_alternatives() {
+        crate::register();
+        turbo_tasks_testing::VcStorage::with(async {
+            let root = VirtualFileSystem::new().root();
+            let source = |name: &str, code: &str| -> Vc<Box<dyn Source>> {
+                Vc::upcast(VirtualSource::new(
+                    root.join(name.into()),
+                    AssetContent::file(File::from(code).into()),
+                ))
+            };
+            let sources = Vc::<Sources>::cell(vec![
+                source("not-a-runtime.js", "module.exports = {};")
+                    .to_resolved()
+                    .await?,
+                source(
+                    "runtime.js",
+                    r#"(() => {
+                        function __webpack_require__(moduleId) {}
+                        __webpack_require__.u = (chunkId) => "" + chunkId + ".js";
+                        __webpack_require__.f.require = (chunkId, promises) => {
+                            require("./chunks/" + __webpack_require__.u(chunkId));
+                        };
+                    })();"#,
+                )
+                .to_resolved()
+                .await?,
+            ]);
+
+            let runtime =
+                first_webpack_runtime(sources, EcmascriptInputTransforms::empty()).await?;
+            let WebpackRuntime::Webpack5 {
+                chunk_request_expr, ..
+            } = &*runtime
+            else {
+                panic!("expected the second source to be a webpack runtime");
+            };
+            assert_eq!(
+                chunk_request(chunk_request_expr, "179").as_deref(),
+                Some("./chunks/179.js")
+            );
+            anyhow::Ok(())
+        })
+        .await
+        .unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use anyhow::{bail, Result};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{ResolvedVc, Value, Vc};
+use turbo_tasks_fs::{glob::Glob, File, FileSystem, FileSystemPath, VirtualFileSystem};
+use turbo_tasks_testing::{register, run, Registration};
+use turbopack_core::{
+    asset::AssetContent,
+    compile_time_info::CompileTimeInfo,
+    context::{AssetContext, ProcessResult},
+    environment::{Environment, ExecutionEnvironment, NodeJsEnvironment},
+    issue::{Issue, IssueDescriptionExt, IssueSeverity},
+    reference_type::ReferenceType,
+    resolve::{options::ResolveOptions, parse::Request, ModuleResolveResult, ResolveResult},
+    source::Source,
+    virtual_source::VirtualSource,
+};
+use turbopack_ecmascript::{
+    references::{esm::EsmAssetReference, unresolved_reference::UnresolvedReferenceKind},
+    AnalyzeEcmascriptModuleResult, EcmascriptInputTransforms, EcmascriptModuleAsset,
+    EcmascriptModuleAssetType, EcmascriptOptions, SpecifiedModuleType,
+};
+
+static REGISTRATION: Registration = register!(turbopack_ecmascript::register);
+
+/// An [AssetContext] for analysing a module on its own. The analysis only
+/// creates references and never resolves them, so none of the methods are
+/// expected to be called.
+#[turbo_tasks::value]
+struct IsolatedAssetContext;
+
+#[turbo_tasks::value_impl]
+impl AssetContext for IsolatedAssetContext {
+    #[turbo_tasks::function]
+    fn compile_time_info(&self) -> Result<Vc<CompileTimeInfo>> {
+        bail!("the module is analysed on its own")
+    }
+
+    #[turbo_tasks::function]
+    fn layer(&self) -> Result<Vc<RcStr>> {
+        bail!("the module is analysed on its own")
+    }
+
+    #[turbo_tasks::function]
+    fn resolve_options(
+        &self,
+        _origin_path: Vc<FileSystemPath>,
+        _reference_type: Value<ReferenceType>,
+    ) -> Result<Vc<ResolveOptions>> {
+        bail!("the module is analysed on its own")
+    }
+
+    #[turbo_tasks::function]
+    fn resolve_asset(
+        &self,
+        _origin_path: Vc<FileSystemPath>,
+        _request: Vc<Request>,
+        _resolve_options: Vc<ResolveOptions>,
+        _reference_type: Value<ReferenceType>,
+    ) -> Result<Vc<ModuleResolveResult>> {
+        bail!("the module is analysed on its own")
+    }
+
+    #[turbo_tasks::function]
+    fn process(
+        &self,
+        _asset: Vc<Box<dyn Source>>,
+        _reference_type: Value<ReferenceType>,
+    ) -> Result<Vc<ProcessResult>> {
+        bail!("the module is analysed on its own")
+    }
+
+    #[turbo_tasks::function]
+    fn process_resolve_result(
+        &self,
+        _result: Vc<ResolveResult>,
+        _reference_type: Value<ReferenceType>,
+    ) -> Result<Vc<ModuleResolveResult>> {
+        bail!("the module is analysed on its own")
+    }
+
+    #[turbo_tasks::function]
+    fn with_transition(&self, _transition: RcStr) -> Result<Vc<Box<dyn AssetContext>>> {
+        bail!("the module is analysed on its own")
+    }
+
+    #[turbo_tasks::function]
+    fn side_effect_free_packages(&self) -> Result<Vc<Glob>> {
+        bail!("the module is analysed on its own")
+    }
+}
+
+/// Analyses `code` as the module `index.js` targeting Node.js.
+fn analyze(code: &str, options: EcmascriptOptions) -> Vc<AnalyzeEcmascriptModuleResult> {
+    let source = VirtualSource::new(
+        VirtualFileSystem::new().root().join("index.js".into()),
+        AssetContent::file(File::from(code).into()),
+    );
+    let compile_time_info = CompileTimeInfo::new(Environment::new(Value::new(
+        ExecutionEnvironment::NodeJsLambda(NodeJsEnvironment::default().resolved_cell()),
+    )));
+    EcmascriptModuleAsset::new(
+        Vc::upcast(source),
+        Vc::upcast(IsolatedAssetContext.cell()),
+        Value::new(EcmascriptModuleAssetType::Ecmascript),
+        EcmascriptInputTransforms::empty(),
+        options.cell(),
+        compile_time_info,
+    )
+    .analyze()
+}
+
+#[tokio::test]
+async fn unresolved_dynamic_import() {
+    run(&REGISTRATION, || async {
+        let result = analyze(
+            "import(variable);",
+            EcmascriptOptions {
+                specified_module_type: SpecifiedModuleType::EcmaScript,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let unresolved_references = result.unresolved_references.await?;
+        assert_eq!(
+            unresolved_references
+                .iter()
+                .map(|reference| &reference.kind)
+                .collect::<Vec<_>>(),
+            [&UnresolvedReferenceKind::DynamicImport]
+        );
+        assert!(!unresolved_references[0].args_repr.is_empty());
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn unresolved_commonjs_requests() {
+    run(&REGISTRATION, || async {
+        let result = analyze(
+            r#"
+                const fs = require("fs");
+                require(variable);
+                require.resolve(variable);
+                fs.readFileSync(variable);
+                require("./constant");
+            "#,
+            EcmascriptOptions {
+                specified_module_type: SpecifiedModuleType::CommonJs,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let unresolved_references = result.unresolved_references.await?;
+        assert_eq!(
+            unresolved_references
+                .iter()
+                .map(|reference| &reference.kind)
+                .collect::<Vec<_>>(),
+            [
+                &UnresolvedReferenceKind::Require,
+                &UnresolvedReferenceKind::RequireResolve,
+                &UnresolvedReferenceKind::FsReadMethod("readFileSync".into()),
+            ]
+        );
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn passthrough_dynamic_import() {
+    run(&REGISTRATION, || async {
+        let result_vc = analyze(
+            "import(runtimeVar);",
+            EcmascriptOptions {
+                specified_module_type: SpecifiedModuleType::EcmaScript,
+                passthrough_dynamic_imports: true,
+                ..Default::default()
+            },
+        );
+        let result = result_vc.resolve_strongly_consistent().await?.await?;
+
+        let unresolved_references = result.unresolved_references.await?;
+        assert_eq!(unresolved_references.len(), 1);
+        assert_eq!(
+            unresolved_references[0].kind,
+            UnresolvedReferenceKind::DynamicImport
+        );
+        assert!(unresolved_references[0].passthrough);
+
+        let captured_issues = result_vc.peek_issues_with_path().await?;
+        assert!(!captured_issues.is_empty_ref());
+        for issue in captured_issues.iter() {
+            assert_eq!(*issue.severity().await?, IssueSeverity::Info);
+        }
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn import_and_reexport_share_a_reference() {
+    run(&REGISTRATION, || async {
+        let result = analyze(
+            r#"
+                import { a } from "./dep.js";
+                export { b } from "./dep.js";
+                console.log(a);
+            "#,
+            EcmascriptOptions {
+                specified_module_type: SpecifiedModuleType::EcmaScript,
+                tree_shaking_mode: None,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let mut esm_references = 0;
+        for &reference in result.references.await?.iter() {
+            if ResolvedVc::try_downcast_type::<EsmAssetReference>(reference)
+                .await?
+                .is_some()
+            {
+                esm_references += 1;
+            }
+        }
+        assert_eq!(esm_references, 1);
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
@@ -54,11 +54,15 @@ pub async fn apply_esm_specific_options(
         conditions.insert("require".into(), ConditionValue::Unset);
     }
 
-    if matches!(
-        reference_type.into_value(),
-        ReferenceType::EcmaScriptModules(EcmaScriptModulesReferenceSubType::ImportWithType(_))
-    ) {
-        options.extensions.clear();
+    if let ReferenceType::EcmaScriptModules(EcmaScriptModulesReferenceSubType::ImportWithType(ty)) =
+        reference_type.into_value()
+    {
+        options.extensions = options
+            .import_type_extensions
+            .iter()
+            .find(|(import_type, _)| import_type == ty.as_str())
+            .map(|(_, extensions)| extensions.clone())
+            .unwrap_or_default();
     }
 
     Ok(options.into())
@@ -6,7 +6,7 @@ use turbo_rcstr::RcStr;
 use turbo_tasks::{FxIndexMap, ResolvedVc, TryFlatJoinIterExt, TryJoinIterExt, ValueToString, Vc};
 use turbo_tasks_fs::{
     glob::Glob, json::parse_json_rope_with_source_context, DirectoryEntry, FileContent,
-    FileSystemEntryType, FileSystemPath,
+    FileSystemEntryType, FileSystemPath, ReadGlobResult,
 };
 use turbopack_core::{
     asset::{Asset, AssetContent},
@@ -83,6 +83,14 @@ impl ValueToString for NodePreGypConfigReference {
     }
 }
 
+/// Returns the results of a glob sorted by path, so the resolved modules are
+/// in the same order regardless of the order of the directory listing.
+fn sorted_glob_results(glob_result: &ReadGlobResult) -> Vec<(&String, &DirectoryEntry)> {
+    let mut results = glob_result.results.iter().collect::<Vec<_>>();
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+    results
+}
+
 #[turbo_tasks::function]
 pub async fn resolve_node_pre_gyp_files(
     context_dir: Vc<FileSystemPath>,
@@ -135,16 +143,14 @@ pub async fn resolve_node_pre_gyp_files(
                         )
                         .into();
 
-                    for (key, entry) in config_file_dir
+                    let dylibs = config_file_dir
                         .join(native_binding_path.clone())
                         .read_glob(
                             Glob::new(format!("*.{}", compile_target.dylib_ext()).into()),
                             false,
                         )
-                        .await?
-                        .results
-                        .iter()
-                    {
+                        .await?;
+                    for (key, entry) in sorted_glob_results(&dylibs) {
                         if let &DirectoryEntry::File(dylib) | &DirectoryEntry::Symlink(dylib) =
                             entry
                         {
@@ -168,15 +174,13 @@ pub async fn resolve_node_pre_gyp_files(
                         );
                     }
                 }
-                for (key, entry) in config_file_dir
+                let deps = config_file_dir
                     // TODO
                     // read the dependencies path from `bindings.gyp`
                     .join("deps/lib".into())
                     .read_glob(Glob::new("*".into()), false)
-                    .await?
-                    .results
-                    .iter()
-                {
+                    .await?;
+                for (key, entry) in sorted_glob_results(&deps) {
                     match *entry {
                         DirectoryEntry::File(dylib) => {
                             sources.insert(
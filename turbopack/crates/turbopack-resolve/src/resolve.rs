@@ -1,5 +1,5 @@
 use anyhow::Result;
-use turbo_tasks::Vc;
+use turbo_tasks::{ResolvedVc, Vc};
 use turbo_tasks_fs::{FileSystem, FileSystemPath};
 use turbopack_core::resolve::{
     find_context_file,
@@ -15,8 +15,9 @@ use crate::{
     typescript::{apply_tsconfig_resolve_options, tsconfig, tsconfig_resolve_options},
 };
 
-const NODE_EXTERNALS: [&str; 63] = [
+const NODE_EXTERNALS: [&str; 66] = [
     "assert",
+    "assert/strict",
     "async_hooks",
     "buffer",
     "child_process",
@@ -36,6 +37,7 @@ const NODE_EXTERNALS: [&str; 63] = [
     "http2",
     "https",
     "inspector",
+    "inspector/promises",
     "module",
     "net",
     "os",
@@ -47,6 +49,7 @@ const NODE_EXTERNALS: [&str; 63] = [
     "punycode",
     "querystring",
     "readline",
+    "readline/promises",
     "repl",
     "stream",
     "stream/promises",
@@ -81,6 +84,9 @@ const NODE_EXTERNALS: [&str; 63] = [
     "_stream_writable",
 ];
 
+/// Builtins which are only available with the `node:` prefix.
+const NODE_PREFIXED_EXTERNALS: [&str; 4] = ["sea", "sqlite", "test", "test/reporters"];
+
 const EDGE_NODE_EXTERNALS: [&str; 5] = ["buffer", "events", "assert", "util", "async_hooks"];
 
 #[turbo_tasks::function]
@@ -115,6 +121,13 @@ async fn base_resolve_options(
                     .resolved_cell(),
             );
         }
+        for req in NODE_PREFIXED_EXTERNALS {
+            direct_mappings.insert(
+                AliasPattern::exact(format!("node:{req}")),
+                ImportMapping::External(None, ExternalType::CommonJs, ExternalTraced::Untraced)
+                    .resolved_cell(),
+            );
+        }
     }
     if opt.enable_edge_node_externals {
         for req in EDGE_NODE_EXTERNALS {
@@ -216,21 +229,49 @@ async fn base_resolve_options(
         ext.push(".json".into());
         ext
     };
+    let extensions = if opt.conditional_extensions.is_empty() {
+        extensions
+    } else {
+        let mut ext = Vec::new();
+        for base in extensions {
+            for condition in &opt.conditional_extensions {
+                ext.push(format!("{condition}{base}").into());
+            }
+            ext.push(base);
+        }
+        ext
+    };
     Ok(ResolveOptions {
         extensions,
-        modules: if let Some(environment) = emulating {
-            if *environment.resolve_node_modules().await? {
-                vec![ResolveModules::Nested(
-                    root.to_resolved().await?,
-                    vec!["node_modules".into()],
-                )]
+        import_type_extensions: opt.import_type_extensions.clone(),
+        modules: {
+            let mut mods = if let Some(environment) = emulating {
+                if *environment.resolve_node_modules().await? {
+                    vec![ResolveModules::Nested(
+                        root.to_resolved().await?,
+                        vec!["node_modules".into()],
+                    )]
+                } else {
+                    Vec::new()
+                }
             } else {
-                Vec::new()
-            }
-        } else {
-            let mut mods = Vec::new();
-            if let Some(dir) = opt.enable_node_modules {
-                mods.push(ResolveModules::Nested(dir, vec!["node_modules".into()]));
+                let mut mods = Vec::new();
+                if let Some(dir) = opt.enable_node_modules {
+                    mods.push(ResolveModules::Pnp(dir));
+                    mods.push(ResolveModules::Nested(dir, vec!["node_modules".into()]));
+                }
+                mods
+            };
+            if !opt.global_module_dirs.is_empty() {
+                let excluded_extensions = ResolvedVc::cell(Default::default());
+                mods.extend(
+                    opt.global_module_dirs
+                        .iter()
+                        .map(|&dir| ResolveModules::Path {
+                            dir,
+                            excluded_extensions,
+                        }),
+                );
             }
             mods
         },
@@ -264,12 +305,16 @@ async fn base_resolve_options(
             }
             resolve_in
         },
+        package_overrides: opt.package_overrides.clone(),
         default_files: vec!["index".into()],
         import_map: Some(import_map),
         resolved_map: opt.resolved_map,
+        overlay: opt.overlay,
         plugins,
         before_resolve_plugins: opt.before_resolve_plugins.clone(),
+        fallback_resolve_plugins: opt.fallback_resolve_plugins.clone(),
         loose_errors: opt.loose_errors,
+        preserve_symlinks: opt.preserve_symlinks,
         ..Default::default()
     }
     .into())
@@ -6,7 +6,7 @@ use turbopack_core::{
     condition::ContextCondition,
     environment::Environment,
     resolve::{
-        options::{ImportMap, ResolvedMap},
+        options::{ImportMap, PackageOverride, ResolveOverlay, ResolvedMap},
         plugin::{AfterResolvePlugin, BeforeResolvePlugin},
     },
 };
@@ -29,9 +29,15 @@ pub struct ResolveOptionsContext {
     pub enable_mjs_extension: bool,
     #[serde(default)]
     /// Enable resolving of the node_modules folder when within the provided
-    /// directory
+    /// directory, and of packages listed in a Yarn Plug'n'Play manifest
+    /// (`.pnp.data.json`) in that directory
     pub enable_node_modules: Option<ResolvedVc<FileSystemPath>>,
     #[serde(default)]
+    /// Additional directories to look up bare module requests in, in order,
+    /// after the node_modules lookup. This is what Node.js does with the
+    /// (discouraged) `NODE_PATH` environment variable.
+    pub global_module_dirs: Vec<ResolvedVc<FileSystemPath>>,
+    #[serde(default)]
     /// Mark well-known Node.js modules as external imports and load them using
     /// native `require`. e.g. url, querystring, os
     pub enable_node_externals: bool,
@@ -47,8 +53,25 @@ pub struct ResolveOptionsContext {
     #[serde(default)]
     pub custom_conditions: Vec<RcStr>,
     #[serde(default)]
+    /// The extensions tried in order for extensionless requests, also for
+    /// `index` files of directories. When unset, this is the extensions of
+    /// the emulated environment or otherwise `.tsx`, `.ts`, `.jsx`, `.js`,
+    /// `.mjs`, `.node`, `.json`, depending on the enabled features.
     pub custom_extensions: Option<Vec<RcStr>>,
     #[serde(default)]
+    /// Extensions for imports with a `type` attribute, by attribute value. See
+    /// `ResolveOptions::import_type_extensions`.
+    pub import_type_extensions: Vec<(RcStr, Vec<RcStr>)>,
+    #[serde(default)]
+    /// Packages which resolve to a specific copy, by package name. See
+    /// `ResolveOptions::package_overrides`.
+    pub package_overrides: Vec<(RcStr, PackageOverride)>,
+    #[serde(default)]
+    /// Suffixes which are tried before each resolve extension, in order. With
+    /// `.server`, `./component` resolves to `./component.server.js` before
+    /// `./component.js`. Usually differs per build target.
+    pub conditional_extensions: Vec<RcStr>,
+    #[serde(default)]
     /// An additional import map to use when resolving modules.
     ///
     /// If set, this import map will be applied to `ResolveOption::import_map`.
@@ -66,6 +89,10 @@ pub struct ResolveOptionsContext {
     /// An additional resolved map to use after modules have been resolved.
     pub resolved_map: Option<ResolvedVc<ResolvedMap>>,
     #[serde(default)]
+    /// Sources which resolve as if they existed on disk. See
+    /// `ResolveOptions::overlay`.
+    pub overlay: Option<ResolvedVc<ResolveOverlay>>,
+    #[serde(default)]
     /// A list of rules to use a different resolve option context for certain
     /// context paths. The first matching is used.
     pub rules: Vec<(ContextCondition, ResolvedVc<ResolveOptionsContext>)>,
@@ -73,8 +100,15 @@ pub struct ResolveOptionsContext {
     /// Plugins which get applied before and after resolving.
     pub after_resolve_plugins: Vec<ResolvedVc<Box<dyn AfterResolvePlugin>>>,
     pub before_resolve_plugins: Vec<ResolvedVc<Box<dyn BeforeResolvePlugin>>>,
+    #[serde(default)]
+    /// Plugins which get applied when a request could not be resolved.
+    pub fallback_resolve_plugins: Vec<ResolvedVc<Box<dyn BeforeResolvePlugin>>>,
     /// Warn instead of error for resolve errors
     pub loose_errors: bool,
+    #[serde(default)]
+    /// Keep symlinked paths instead of resolving them to their real path. See
+    /// `ResolveOptions::preserve_symlinks`.
+    pub preserve_symlinks: bool,
 
     #[serde(default)]
     pub placeholder_for_future_extensions: (),
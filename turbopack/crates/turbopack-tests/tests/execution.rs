@@ -12,14 +12,14 @@ use dunce::canonicalize;
 use serde::{Deserialize, Serialize};
 use turbo_rcstr::RcStr;
 use turbo_tasks::{
-    apply_effects, debug::ValueDebugFormat, fxindexmap, trace::TraceRawVcs, Completion, ResolvedVc,
-    TryJoinIterExt, TurboTasks, Value, Vc,
+    apply_effects, debug::ValueDebugFormat, fxindexmap, trace::TraceRawVcs, Completion, FxIndexMap,
+    ResolvedVc, TryJoinIterExt, TurboTasks, Value, Vc,
 };
 use turbo_tasks_bytes::stream::SingleValue;
 use turbo_tasks_env::CommandLineProcessEnv;
 use turbo_tasks_fs::{
-    json::parse_json_with_source_context, util::sys_to_unix, DiskFileSystem, FileContent,
-    FileSystem, FileSystemEntryType, FileSystemPath,
+    glob::Glob, json::parse_json_with_source_context, util::sys_to_unix, DiskFileSystem, File,
+    FileContent, FileSystem, FileSystemEntryType, FileSystemPath,
 };
 use turbo_tasks_memory::MemoryBackend;
 use turbopack::{
@@ -28,6 +28,7 @@ use turbopack::{
     ModuleAssetContext,
 };
 use turbopack_core::{
+    asset::AssetContent,
     compile_time_defines,
     compile_time_info::CompileTimeInfo,
     condition::ContextCondition,
@@ -37,10 +38,13 @@ use turbopack_core::{
     issue::{Issue, IssueDescriptionExt},
     reference_type::{InnerAssets, ReferenceType},
     resolve::{
-        options::{ImportMap, ImportMapping},
-        ExternalTraced, ExternalType,
+        options::{ImportMap, ImportMapping, PackageOverride},
+        parse::Request,
+        plugin::{BeforeResolvePlugin, BeforeResolvePluginCondition},
+        ExternalTraced, ExternalType, ResolveResult, ResolveResultOption,
     },
     source::Source,
+    virtual_source::VirtualSource,
 };
 use turbopack_ecmascript_runtime::RuntimeType;
 use turbopack_node::{debug::should_debug, evaluate::evaluate};
@@ -197,6 +201,97 @@ async fn run_inner(
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct TestOptions {
     tree_shaking_mode: Option<TreeShakingMode>,
+    /// Directories relative to the test directory which are used like `NODE_PATH`.
+    #[serde(default)]
+    global_module_dirs: Vec<RcStr>,
+    /// Requests which are replaced when they can't be resolved, like webpack's
+    /// `resolve.fallback`.
+    #[serde(default)]
+    resolve_fallback: FxIndexMap<RcStr, ResolveFallback>,
+    /// Enables `EcmascriptOptionsContext::respect_package_files`.
+    #[serde(default)]
+    respect_package_files: bool,
+    /// Sets `ResolveOptionsContext::custom_extensions`, in priority order.
+    #[serde(default)]
+    custom_extensions: Option<Vec<RcStr>>,
+    /// Sets `ResolveOptionsContext::conditional_extensions`, e.g. `[".server"]`.
+    #[serde(default)]
+    conditional_extensions: Vec<RcStr>,
+    /// Files relative to the `input` directory which only exist in
+    /// `ResolveOptionsContext::overlay`, mapped to their content.
+    #[serde(default)]
+    resolve_overlay: FxIndexMap<RcStr, RcStr>,
+    /// Pins packages to a directory relative to the `input` directory (when
+    /// starting with `./`) or to a semver range.
+    #[serde(default)]
+    package_overrides: FxIndexMap<RcStr, RcStr>,
+    /// Sets `ResolveOptionsContext::import_type_extensions`.
+    #[serde(default)]
+    import_type_extensions: FxIndexMap<RcStr, Vec<RcStr>>,
+    /// Registers a [RequestModuleResolvePlugin] for each entry.
+    #[serde(default)]
+    resolve_plugins: Vec<ResolvePluginOptions>,
+    /// Enables `ResolveOptionsContext::enable_node_externals`.
+    #[serde(default)]
+    node_externals: bool,
+    /// Enables `ResolveOptionsContext::preserve_symlinks`.
+    #[serde(default)]
+    preserve_symlinks: bool,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, TraceRawVcs, ValueDebugFormat)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ResolvePluginOptions {
+    /// A glob of the requests the plugin resolves.
+    request: RcStr,
+    /// Only apply the plugin to requests which can't be resolved otherwise.
+    #[serde(default)]
+    fallback: bool,
+}
+
+/// Resolves the matching requests to a generated module which exports the
+/// request string.
+#[turbo_tasks::value]
+struct RequestModuleResolvePlugin {
+    request_glob: RcStr,
+    root: ResolvedVc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl BeforeResolvePlugin for RequestModuleResolvePlugin {
+    #[turbo_tasks::function]
+    fn before_resolve_condition(&self) -> Vc<BeforeResolvePluginCondition> {
+        BeforeResolvePluginCondition::from_request_glob(Glob::new(self.request_glob.clone()))
+    }
+
+    #[turbo_tasks::function]
+    async fn before_resolve(
+        &self,
+        _lookup_path: Vc<FileSystemPath>,
+        _reference_type: Value<ReferenceType>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ResolveResultOption>> {
+        let Some(request) = request.await?.request() else {
+            return Ok(ResolveResultOption::none());
+        };
+        let path = self
+            .root
+            .join(format!("{}.js", request.replace([':', '/'], "_")).into());
+        let content = format!("module.exports = {};", serde_json::to_string(&*request)?);
+        let source = VirtualSource::new(path, AssetContent::file(File::from(content).into()));
+        Ok(ResolveResultOption::some(
+            ResolveResult::source(ResolvedVc::upcast(source.to_resolved().await?)).cell(),
+        ))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, TraceRawVcs, ValueDebugFormat)]
+#[serde(untagged)]
+enum ResolveFallback {
+    /// A request resolved relative to the test's `input` directory.
+    Request(RcStr),
+    /// `false` ignores the request, like the `browser` field does.
+    Ignore(bool),
 }
 
 #[turbo_tasks::value]
@@ -288,6 +383,11 @@ async fn run_test(prepared_test: Vc<PreparedTest>) -> Result<Vc<RunTestResult>>
         .cell()
         .await?;
 
+    let mut global_module_dirs = Vec::new();
+    for dir in &options.global_module_dirs {
+        global_module_dirs.push(project_path.join(dir.clone()).to_resolved().await?);
+    }
+
     let mut import_map = ImportMap::empty();
     import_map.insert_wildcard_alias(
         "esm-external/",
@@ -309,6 +409,56 @@ async fn run_test(prepared_test: Vc<PreparedTest>) -> Result<Vc<RunTestResult>>
             .resolved_cell(),
     );
 
+    let mut fallback_import_map = ImportMap::empty();
+    let input_path = project_path.join("input".into()).to_resolved().await?;
+    for (request, fallback) in &options.resolve_fallback {
+        let mapping = match fallback {
+            ResolveFallback::Request(fallback) => {
+                ImportMapping::PrimaryAlternative(fallback.clone(), Some(input_path))
+            }
+            ResolveFallback::Ignore(_) => ImportMapping::Ignore,
+        };
+        fallback_import_map.insert_exact_alias(request.clone(), mapping.resolved_cell());
+    }
+
+    let mut overlay = FxIndexMap::default();
+    for (path, content) in &options.resolve_overlay {
+        let path = input_path.join(path.clone());
+        let source =
+            VirtualSource::new(path, AssetContent::file(File::from(content.clone()).into()));
+        overlay.insert(
+            path.to_resolved().await?,
+            ResolvedVc::upcast(source.to_resolved().await?),
+        );
+    }
+
+    let mut package_overrides = Vec::new();
+    for (name, value) in &options.package_overrides {
+        let package_override = if value.starts_with("./") {
+            PackageOverride::Path(input_path.join(value.clone()).to_resolved().await?)
+        } else {
+            PackageOverride::Version(value.clone())
+        };
+        package_overrides.push((name.clone(), package_override));
+    }
+
+    let mut before_resolve_plugins = Vec::new();
+    let mut fallback_resolve_plugins = Vec::new();
+    for plugin in &options.resolve_plugins {
+        let resolve_plugin = ResolvedVc::upcast(
+            RequestModuleResolvePlugin {
+                request_glob: plugin.request.clone(),
+                root: input_path,
+            }
+            .resolved_cell(),
+        );
+        if plugin.fallback {
+            fallback_resolve_plugins.push(resolve_plugin);
+        } else {
+            before_resolve_plugins.push(resolve_plugin);
+        }
+    }
+
     let asset_context: Vc<Box<dyn AssetContext>> = Vc::upcast(ModuleAssetContext::new(
         Default::default(),
         compile_time_info,
@@ -318,6 +468,7 @@ async fn run_test(prepared_test: Vc<PreparedTest>) -> Result<Vc<RunTestResult>>
                     TypescriptTransformOptions::default().resolved_cell(),
                 ),
                 import_externals: true,
+                respect_package_files: options.respect_package_files,
                 ..Default::default()
             },
             preset_env_versions: Some(env),
@@ -325,6 +476,10 @@ async fn run_test(prepared_test: Vc<PreparedTest>) -> Result<Vc<RunTestResult>>
             rules: vec![(
                 ContextCondition::InDirectory("node_modules".into()),
                 ModuleOptionsContext {
+                    ecmascript: EcmascriptOptionsContext {
+                        respect_package_files: options.respect_package_files,
+                        ..Default::default()
+                    },
                     tree_shaking_mode: options.tree_shaking_mode,
                     ..Default::default()
                 }
@@ -350,6 +505,21 @@ async fn run_test(prepared_test: Vc<PreparedTest>) -> Result<Vc<RunTestResult>>
             browser: true,
             module: true,
             import_map: Some(import_map.resolved_cell()),
+            fallback_import_map: Some(fallback_import_map.resolved_cell()),
+            global_module_dirs,
+            custom_extensions: options.custom_extensions.clone(),
+            conditional_extensions: options.conditional_extensions.clone(),
+            package_overrides,
+            before_resolve_plugins,
+            fallback_resolve_plugins,
+            import_type_extensions: options
+                .import_type_extensions
+                .iter()
+                .map(|(ty, extensions)| (ty.clone(), extensions.clone()))
+                .collect(),
+            overlay: (!overlay.is_empty()).then(|| ResolvedVc::cell(overlay)),
+            enable_node_externals: options.node_externals,
+            preserve_symlinks: options.preserve_symlinks,
             ..Default::default()
         }
         .cell(),
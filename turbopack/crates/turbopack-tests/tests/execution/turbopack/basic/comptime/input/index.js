@@ -128,6 +128,20 @@ it("should evaluate NODE_ENV", () => {
   }
 });
 
+it("should evaluate NODE_ENV comparisons in any form", () => {
+  if (process.env.NODE_ENV === "production") {
+    require("fail");
+    import("fail");
+  }
+  if ("development" != process.env.NODE_ENV) {
+    require("fail");
+    import("fail");
+  }
+  const ok =
+    process.env.NODE_ENV === "development" ? require("./ok") : require("fail");
+  expect(ok).toBeDefined();
+});
+
 it("should keep side-effects in if statements", () => {
   {
     let ok = false;
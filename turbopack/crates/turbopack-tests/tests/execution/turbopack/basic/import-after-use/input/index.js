@@ -0,0 +1,13 @@
+it("should bind imports that are used before the import statement", () => {
+  expect(getValue()).toBe(42);
+  expect(describeDep()).toBe("dep");
+});
+
+function getValue() {
+  return value;
+}
+
+const describeDep = () => dep.name;
+
+import { value } from "./value.js";
+import * as dep from "./dep.js";
@@ -0,0 +1,37 @@
+import text from "./file.txt" with { type: "text" };
+import bytes from "./file.txt" with { type: "bytes" };
+import json from "./data.jsondata" with { type: "json" };
+import jsonAssert from "./data.jsondata" assert { type: "json" };
+
+it("should import the file content as a string", () => {
+  expect(text).toBe("hello\n");
+});
+
+it("should import the file content as bytes", () => {
+  expect(bytes).toBeInstanceOf(Uint8Array);
+  expect(Array.from(bytes)).toEqual([104, 101, 108, 108, 111, 10]);
+});
+
+it("should import the file content as JSON", () => {
+  expect(json).toEqual({ name: "data", values: [1, 2] });
+});
+
+it("should support the deprecated assert keyword", () => {
+  expect(jsonAssert).toEqual({ name: "data", values: [1, 2] });
+});
+
+it("should apply the type attribute of a dynamic import", async () => {
+  const text = await import("./file.txt", { with: { type: "text" } });
+  expect(text.default).toBe("hello\n");
+
+  const json = await import("./data.jsondata", { assert: { type: "json" } });
+  expect(json.default).toEqual({ name: "data", values: [1, 2] });
+});
+
+it("should ignore options of a dynamic import that aren't constant", async () => {
+  const plain = await import("./plain.js", globalThis.importOptions);
+  expect(plain.value).toBe(42);
+
+  const single = await import("./plain.js");
+  expect(single).toBe(plain);
+});
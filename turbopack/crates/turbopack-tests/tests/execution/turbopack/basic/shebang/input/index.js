@@ -0,0 +1,9 @@
+import { value } from "./cli.mjs";
+
+it("should resolve and require a CommonJS file with a shebang", () => {
+  expect(require("./cli.js")).toBe("commonjs");
+});
+
+it("should resolve and import an ESM file with a shebang", () => {
+  expect(value).toBe("esm");
+});
@@ -0,0 +1 @@
+exports.moduleType = typeof module;
@@ -0,0 +1,17 @@
+import * as mtsInCommonJsPackage from "./commonjs/esm.mts";
+import * as tsInCommonJsPackage from "./commonjs/plain.ts";
+import * as ctsInModulePackage from "./module/cjs.cts";
+import * as tsInModulePackage from "./module/plain.ts";
+
+it("should treat .mts files as ESM regardless of the package type", () => {
+  expect(mtsInCommonJsPackage.moduleType).toBe("undefined");
+});
+
+it("should treat .cts files as CommonJS regardless of the package type", () => {
+  expect(ctsInModulePackage.moduleType).toBe("object");
+});
+
+it("should treat .ts files according to the package type", () => {
+  expect(tsInCommonJsPackage.moduleType).toBe("object");
+  expect(tsInModulePackage.moduleType).toBe("undefined");
+});
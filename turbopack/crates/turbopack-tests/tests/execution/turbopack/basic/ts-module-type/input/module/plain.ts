@@ -0,0 +1 @@
+export const moduleType: string = typeof module;
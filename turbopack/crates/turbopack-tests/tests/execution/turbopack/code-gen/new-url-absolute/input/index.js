@@ -0,0 +1,5 @@
+it("should keep absolute URLs passed to new URL(..., import.meta.url)", () => {
+  const url = new URL("https://example.com/assets/file.txt", import.meta.url);
+  expect(url.origin).toBe("https://example.com");
+  expect(url.href).toBe("https://example.com/assets/file.txt");
+});
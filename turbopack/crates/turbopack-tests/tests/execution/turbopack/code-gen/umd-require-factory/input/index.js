@@ -0,0 +1,6 @@
+import umd from "./umd.js";
+
+it("should resolve requires inside a UMD factory", () => {
+  expect(umd.dep).toBe("dep");
+  expect(umd.fromExports).toBe(true);
+});
@@ -0,0 +1,13 @@
+(function (root, factory) {
+  if (typeof define === "function" && define.amd) {
+    define(["require", "exports", "module"], factory);
+  } else if (typeof module === "object" && module.exports) {
+    factory(require, exports, module);
+  } else {
+    root.umd = {};
+    factory(function () {}, root.umd, { exports: root.umd });
+  }
+})(this, function (require, exports, module) {
+  exports.dep = require("./dep");
+  exports.fromExports = module.exports === exports;
+});
@@ -0,0 +1,18 @@
+const loadLocale = (lang) => import(`./locales/${lang}.js`);
+const requireLocale = (lang) => require(`./locales/${lang}.js`);
+
+it("should bundle every locale matched by a template literal import", async () => {
+  await expect(loadLocale("en")).resolves.toHaveProperty("locale", "en");
+  await expect(loadLocale("de")).resolves.toHaveProperty("locale", "de");
+  await expect(loadLocale("fr")).resolves.toHaveProperty("locale", "fr");
+});
+
+it("should bundle every locale matched by a template literal require", () => {
+  expect(requireLocale("en")).toHaveProperty("locale", "en");
+  expect(requireLocale("de")).toHaveProperty("locale", "de");
+  expect(requireLocale("fr")).toHaveProperty("locale", "fr");
+});
+
+it("should reject locales that don't exist", async () => {
+  await expect(loadLocale("xx")).rejects.toThrowError();
+});
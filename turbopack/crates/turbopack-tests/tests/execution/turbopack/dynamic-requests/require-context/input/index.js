@@ -0,0 +1,17 @@
+it("should only list direct children when not recursive", () => {
+  const ctx = require.context("./dir", false, /\.js$/);
+  expect(ctx.keys()).toEqual(["./a.js", "./b.js"]);
+  expect(ctx("./a.js")).toBe("a");
+});
+
+it("should list nested files when recursive", () => {
+  const ctx = require.context("./dir", true, /\.js$/);
+  expect(ctx.keys()).toEqual(["./a.js", "./b.js", "./sub/c.js"]);
+  expect(ctx("./sub/c.js")).toBe("c");
+});
+
+it("should apply the regex filter to the relative path", () => {
+  const ctx = require.context("./dir", true, /^\.\/(a|sub\/.*)\.js$/);
+  expect(ctx.keys()).toEqual(["./a.js", "./sub/c.js"]);
+  expect(() => ctx("./b.js")).toThrowError();
+});
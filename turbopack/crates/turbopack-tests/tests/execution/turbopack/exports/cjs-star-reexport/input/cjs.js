@@ -0,0 +1,3 @@
+exports.a = "a";
+exports.b = "b";
+module.exports.default = "default";
@@ -0,0 +1,22 @@
+import * as barrel from "./barrel";
+import * as esmoduleBarrel from "./esmodule-barrel";
+
+it("should re-export statically known CommonJS exports", () => {
+  expect(barrel.a).toBe("a");
+  expect(barrel.b).toBe("b");
+  expect(Object.keys(barrel).sort()).toEqual(["a", "b"]);
+});
+
+it("should not re-export the default export of a CommonJS module", () => {
+  expect(barrel.default).toBe(undefined);
+});
+
+it("should not re-export CommonJS exports dynamically", () => {
+  require("./cjs").late = "late";
+  expect(barrel.late).toBe(undefined);
+});
+
+it("should not re-export the __esModule marker", () => {
+  expect(esmoduleBarrel.c).toBe("c");
+  expect(Object.keys(esmoduleBarrel)).toEqual(["c"]);
+});
@@ -25,3 +25,11 @@ it("should not cycle when following the alias field", () => {
   expect(otherPackage).toBe("other-package/index");
   expect(otherPackageSubPath).toBe("other-package/sub-path");
 });
+
+it("should ignore a resolved file mapped to false", () => {
+  expect(require("package/dir/disabled.js")).toEqual({});
+});
+
+it("should ignore a module request mapped to false", () => {
+  expect(require("package/dir/uses-ignored.js")).toEqual({});
+});
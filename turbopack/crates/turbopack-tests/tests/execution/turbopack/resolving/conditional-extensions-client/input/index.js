@@ -0,0 +1,11 @@
+import component from "./component";
+import shared from "./shared";
+
+it("should prefer the client extension over the base extension", () => {
+  expect(component).toBe("client");
+  expect(require("./component")).toHaveProperty("default", "client");
+});
+
+it("should fall back to the base extension", () => {
+  expect(shared).toBe("shared");
+});
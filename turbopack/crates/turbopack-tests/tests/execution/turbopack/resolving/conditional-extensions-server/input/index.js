@@ -0,0 +1,11 @@
+import component from "./component";
+import shared from "./shared";
+
+it("should prefer the server extension over the base extension", () => {
+  expect(component).toBe("server");
+  expect(require("./component")).toHaveProperty("default", "server");
+});
+
+it("should fall back to the base extension", () => {
+  expect(shared).toBe("shared");
+});
@@ -0,0 +1,11 @@
+it("should pick the candidate by the configured extension order", () => {
+  expect(require("./candidates/item")).toBe("js");
+});
+
+it("should fall back to later extensions", () => {
+  expect(require("./candidates/only-ts")).toBe("ts");
+});
+
+it("should use the configured extension order for directory indexes", () => {
+  expect(require("./dir")).toBe("js");
+});
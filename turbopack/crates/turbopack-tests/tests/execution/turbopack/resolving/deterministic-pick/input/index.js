@@ -0,0 +1,7 @@
+it("should pick the candidate by extension priority", () => {
+  expect(require("./candidates/item")).toBe("ts");
+});
+
+it("should resolve the directory index", () => {
+  expect(require("./candidates")).toBe("index");
+});
@@ -0,0 +1,17 @@
+import pkg from "pkg";
+import conditional from "pkg/conditional";
+import first from "pkg/first";
+
+it("should fall back to the next array entry when a target doesn't exist", () => {
+  expect(pkg).toBe("index");
+  expect(require("pkg")).toBe("index");
+});
+
+it("should fall back within conditions", () => {
+  expect(conditional).toBe("conditional");
+  expect(require("pkg/conditional")).toBe("conditional");
+});
+
+it("should use the first array entry when it exists", () => {
+  expect(first).toBe("first");
+});
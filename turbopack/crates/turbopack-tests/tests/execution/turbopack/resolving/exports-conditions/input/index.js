@@ -0,0 +1,7 @@
+it("should pick the export matching a custom condition", () => {
+  expect(require("pkg")).toBe("dev");
+});
+
+it("should pick the export matching the target condition", () => {
+  expect(require("pkg/target")).toBe("browser");
+});
@@ -0,0 +1,20 @@
+import index from "pkg";
+import foo from "pkg/foo";
+
+const requireDeep = (path) => require("pkg/" + path);
+
+it("should resolve the package root through the exports field instead of main", () => {
+  expect(index).toBe("index esm");
+  expect(require("pkg")).toBe("index");
+});
+
+it("should resolve an exported subpath with conditions", () => {
+  expect(foo).toBe("foo esm");
+  expect(require("pkg/foo")).toBe("foo");
+});
+
+it("should not resolve paths that are not exported", () => {
+  expect(requireDeep("foo")).toBe("foo");
+  expect(() => requireDeep("lib/main.js")).toThrow();
+  expect(() => requireDeep("lib/foo.js")).toThrow();
+});
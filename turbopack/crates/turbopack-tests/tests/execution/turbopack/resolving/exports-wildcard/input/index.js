@@ -0,0 +1,13 @@
+it("should resolve the package root through the exports field", () => {
+  expect(require("pkg")).toBe("index");
+});
+
+it("should resolve an identity export of package.json", () => {
+  expect(require("pkg/package.json").version).toBe("1.2.3");
+});
+
+it("should resolve subpaths through a wildcard export", () => {
+  expect(require("pkg/foo")).toBe("foo");
+  expect(require("pkg/nested/bar")).toBe("nested/bar");
+});
+
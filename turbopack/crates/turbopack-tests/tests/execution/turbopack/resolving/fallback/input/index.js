@@ -0,0 +1,9 @@
+import { polyfilled } from "crypto-builtin";
+
+it("should use the fallback for an unresolvable request", () => {
+  expect(polyfilled).toBe(true);
+});
+
+it("should ignore an unresolvable request mapped to false", () => {
+  expect(require("fs-builtin")).toEqual({});
+});
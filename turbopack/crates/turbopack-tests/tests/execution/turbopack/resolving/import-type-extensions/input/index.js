@@ -0,0 +1,14 @@
+import readme from "./readme" with { type: "text" };
+import notes from "./notes" with { type: "text" };
+import data from "./data.json" with { type: "json" };
+import readmeModule from "./readme";
+
+it("should resolve typed imports with the extensions of their type", () => {
+  expect(readme).toBe("# readme\n");
+  expect(notes).toBe("notes\n");
+});
+
+it("should resolve other imports with the general extensions", () => {
+  expect(data).toEqual({ data: true });
+  expect(readmeModule).toBe("readme");
+});
@@ -0,0 +1,12 @@
+it("should resolve node builtins to externals", () => {
+  expect(typeof require("crypto").createHash).toBe("function");
+  expect(typeof require("stream").Readable).toBe("function");
+  expect(typeof require("assert/strict").deepEqual).toBe("function");
+  expect(typeof require("fs/promises").readFile).toBe("function");
+});
+
+it("should resolve node: prefixed builtins to the same externals", () => {
+  expect(require("node:crypto")).toBe(require("crypto"));
+  expect(require("node:path")).toBe(require("path"));
+  expect(require("node:readline/promises")).toBe(require("readline/promises"));
+});
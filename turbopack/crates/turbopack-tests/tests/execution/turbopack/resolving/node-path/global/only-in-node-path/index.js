@@ -0,0 +1 @@
+module.exports = "from NODE_PATH";
@@ -0,0 +1,3 @@
+it("should resolve bare specifiers in global module directories", () => {
+  expect(require("only-in-node-path")).toBe("from NODE_PATH");
+});
@@ -0,0 +1,12 @@
+import generated from "./generated";
+import manifest from "./routes/manifest.json";
+import shadowed from "./shadowed.js";
+
+it("should resolve a file which only exists in the overlay", () => {
+  expect(generated).toBe("generated");
+  expect(manifest.routes).toEqual(["/", "/about"]);
+});
+
+it("should prefer the overlay over a file on disk", () => {
+  expect(shadowed).toBe("overlay");
+});
@@ -0,0 +1,5 @@
+import keys from "pkg";
+
+it("should skip files excluded by the package's files field", () => {
+  expect(keys.sort()).toEqual(["./index.js", "./lib/a.js"]);
+});
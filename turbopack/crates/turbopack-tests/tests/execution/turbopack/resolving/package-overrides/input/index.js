@@ -0,0 +1,7 @@
+it("should resolve a package pinned to a path instead of node_modules", () => {
+  expect(require("pinned")).toBe("vendor");
+});
+
+it("should skip nearer copies which don't satisfy the pinned version", () => {
+  expect(require("./nested")).toBe("root");
+});
@@ -0,0 +1,26 @@
+import main from "nested-pkg";
+import merge from "nested-pkg/fp/merge";
+import fp from "nested-pkg/fp";
+import deepObject from "nested-pkg/fp/deep/object";
+import config from "nested-pkg/data/config";
+
+it("should resolve the package root through the main field", () => {
+  expect(main).toBe("main");
+});
+
+it("should probe extensions for subpaths of a package", () => {
+  expect(merge).toBe("fp/merge");
+  expect(config).toEqual({ name: "config" });
+});
+
+it("should fall back to index files for subpath directories", () => {
+  expect(fp).toBe("fp/index");
+  expect(deepObject).toBe("fp/deep/object/index");
+});
+
+it("should resolve subpaths with require", () => {
+  expect(require("nested-pkg/fp/merge").default).toBe("fp/merge");
+  expect(require("nested-pkg/fp/deep/object").default).toBe(
+    "fp/deep/object/index"
+  );
+});
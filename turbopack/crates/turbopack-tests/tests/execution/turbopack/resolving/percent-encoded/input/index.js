@@ -0,0 +1,5 @@
+import ab from "./a%20b.js";
+
+it("should percent-decode relative specifiers", () => {
+  expect(ab).toBe("a b");
+});
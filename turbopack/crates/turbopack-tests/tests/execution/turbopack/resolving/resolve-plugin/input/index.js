@@ -0,0 +1,10 @@
+import star from "svg-icon:star";
+
+it("should resolve a custom scheme with a plugin", () => {
+  expect(star).toBe("svg-icon:star");
+});
+
+it("should only apply fallback plugins to unresolvable requests", () => {
+  expect(require("icons/missing")).toBe("icons/missing");
+  expect(require("icons/present")).toBe("from disk");
+});
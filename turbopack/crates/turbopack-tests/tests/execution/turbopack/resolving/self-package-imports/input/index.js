@@ -0,0 +1,12 @@
+import dep from "#dep";
+import sub from "my-pkg/sub";
+
+it("should resolve package imports with the active conditions", () => {
+  expect(dep).toBe("dep esm");
+  expect(require("#dep")).toBe("dep cjs");
+});
+
+it("should resolve a self-reference with the active conditions", () => {
+  expect(sub).toBe("sub esm");
+  expect(require("my-pkg/sub")).toBe("sub cjs");
+});
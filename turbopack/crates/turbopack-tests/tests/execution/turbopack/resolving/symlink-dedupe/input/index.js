@@ -0,0 +1,6 @@
+import { instance as fromReal } from "./real/shared.js";
+import { instance as fromLink } from "./linked/shared.js";
+
+it("should resolve a file reached through a symlink to a single module", () => {
+  expect(fromLink).toBe(fromReal);
+});
@@ -0,0 +1,6 @@
+import { instance as fromReal } from "./real/shared.js";
+import { instance as fromLink } from "./linked/shared.js";
+
+it("should keep a file reached through a symlink as a separate module", () => {
+  expect(fromLink).not.toBe(fromReal);
+});
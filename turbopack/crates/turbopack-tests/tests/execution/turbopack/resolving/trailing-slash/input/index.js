@@ -0,0 +1,10 @@
+import withSlash from "./utils/";
+import withoutSlash from "./utils";
+
+it("should resolve a request with a trailing slash to the directory", () => {
+  expect(withSlash).toBe("directory");
+});
+
+it("should prefer the file for a request without a trailing slash", () => {
+  expect(withoutSlash).toBe("file");
+});
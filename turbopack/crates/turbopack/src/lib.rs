@@ -58,6 +58,7 @@ pub use turbopack_css as css;
 pub use turbopack_ecmascript as ecmascript;
 use turbopack_ecmascript::{
     references::external_module::{CachedExternalModule, CachedExternalType},
+    text::{BytesContentFileSource, TextContentFileSource},
     tree_shake::asset::EcmascriptModulePartAsset,
 };
 use turbopack_json::JsonModuleAsset;
@@ -514,6 +515,25 @@ async fn process_default_internal(
         _ => None,
     };
 
+    if let ReferenceType::EcmaScriptModules(EcmaScriptModulesReferenceSubType::ImportWithType(
+        ty @ (ImportWithType::Text | ImportWithType::Bytes),
+    )) = &reference_type
+    {
+        // The content is turned into a JS module, which is processed like any other import.
+        let source = match ty {
+            ImportWithType::Text => Vc::upcast(TextContentFileSource::new(source)),
+            _ => Vc::upcast(BytesContentFileSource::new(source)),
+        };
+        return Ok(process_default(
+            module_asset_context,
+            source,
+            Value::new(ReferenceType::EcmaScriptModules(
+                EcmaScriptModulesReferenceSubType::Import,
+            )),
+            processed_rules,
+        ));
+    }
+
     let mut has_type_attribute = false;
 
     let mut current_source = source;
@@ -523,6 +543,7 @@ async fn process_default_internal(
 
             match ty {
                 ImportWithType::Json => Some(ModuleType::Json),
+                ImportWithType::Text | ImportWithType::Bytes => None,
             }
         }
         _ => None,
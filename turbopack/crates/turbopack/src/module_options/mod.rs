@@ -76,6 +76,8 @@ impl ModuleOptions {
                     ref enable_typescript_transform,
                     ref enable_decorators,
                     ignore_dynamic_requests,
+                    passthrough_dynamic_imports,
+                    respect_package_files,
                     import_externals,
                     esm_url_rewrite_behavior,
                     ref enable_typeof_window_inlining,
@@ -132,6 +134,8 @@ impl ModuleOptions {
             url_rewrite_behavior: esm_url_rewrite_behavior,
             import_externals,
             ignore_dynamic_requests,
+            passthrough_dynamic_imports,
+            respect_package_files,
             refresh,
             ..Default::default()
         };
@@ -160,6 +160,14 @@ pub struct EcmascriptOptionsContext {
     /// If false, they will reference the whole directory. If true, they won't
     /// reference anything and lead to an runtime error instead.
     pub ignore_dynamic_requests: bool,
+    /// Leave `import()` calls without any static known part untouched, so
+    /// they are resolved by the runtime's module system. Only useful for
+    /// server and edge targets.
+    pub passthrough_dynamic_imports: bool,
+    /// Skip files which are not published with their package (excluded by
+    /// the `files` field or `.npmignore`) when `require.context()` enumerates
+    /// a directory inside of `node_modules`.
+    pub respect_package_files: bool,
 
     pub placeholder_for_future_extensions: (),
 }
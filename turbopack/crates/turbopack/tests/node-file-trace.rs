@@ -82,6 +82,7 @@ static ALLOC: turbo_tasks_malloc::TurboMalloc = turbo_tasks_malloc::TurboMalloc;
 #[case::core_js("integration/core-js.js")]
 #[case::cosmosdb_query("integration/cosmosdb-query.js")]
 #[case::cowsay("integration/cowsay.js")]
+#[case::create_require("integration/create-require.mjs")]
 #[cfg_attr(
     not(feature = "bench_against_node_nft"),
     should_panic(expected = "Error: Cannot find module '../../out/node-file-trace'"),
@@ -214,6 +215,7 @@ static ALLOC: turbo_tasks_malloc::TurboMalloc = turbo_tasks_malloc::TurboMalloc;
         .expected_stderr("Error [ERR_MODULE_NOT_FOUND]: Cannot find module")
 )]
 #[case::read_file("integration/read-file.mjs")]
+#[case::read_file_destructured("integration/read-file-destructured.mjs")]
 #[cfg_attr(
     not(feature = "bench_against_node_nft"),
     //[TODO]: WEB-1188 reenable once fixed.
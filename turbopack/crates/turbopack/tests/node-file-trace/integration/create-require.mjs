@@ -0,0 +1,5 @@
+import { createRequire } from "module";
+
+const require = createRequire(import.meta.url);
+
+console.log(require("./content/hello.json"));
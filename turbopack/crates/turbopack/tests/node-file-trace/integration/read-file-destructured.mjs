@@ -0,0 +1,16 @@
+import { readFileSync } from "fs";
+import { join, resolve } from "path";
+
+function getData() {
+  return JSON.parse(
+    readFileSync(join(process.cwd(), "content/hello.json"), "utf8")
+  );
+}
+
+function getDataResolved() {
+  return JSON.parse(
+    readFileSync(resolve(process.cwd(), "content/hello.json"), "utf8")
+  );
+}
+
+console.log(getData(), getDataResolved());